@@ -0,0 +1,101 @@
+use chrono::NaiveDate;
+use criterion::{criterion_group, criterion_main, Criterion, Throughput};
+use std::hint::black_box;
+use temporis::{parse_date, recognition, Parser};
+
+const ISO: &[&str] = &["2024-01-16", "16/01/2024", "2024/1/6", "16-01-24"];
+const KEYWORDS: &[&str] = &["today", "Tomorrow", "friday", "eom", "eonq", "sow", "nfri"];
+const RELATIVE: &[&str] = &["3d", "-2weeks", "6m", "1y", "2mon", "15th", "-1st"];
+const WRITTEN: &[&str] = &[
+    "16 jan 2024",
+    "January 16, 2024",
+    "tue 16 jan 2024",
+    "jan. 16",
+    "16th of january",
+    "mar-2025",
+    "march",
+];
+const NESTED: &[&str] = &[
+    "2nd tue of march",
+    "last fri of month",
+    "last day of next month",
+    "15th of next month",
+    "3rd of eoq",
+];
+const WORDS: &[&str] = &[
+    "three days",
+    "a couple of weeks",
+    "twenty-first of march",
+    "first monday",
+];
+const UNRECOGNIZED: &[&str] = &["tomorow", "xyz", "feb 30 2024"];
+
+fn bench_parse(c: &mut Criterion) {
+    let parser = Parser::new();
+    let today = NaiveDate::from_ymd_opt(2026, 10, 18).unwrap();
+
+    let mut group = c.benchmark_group("parse");
+    for (name, inputs) in [
+        ("iso", ISO),
+        ("keywords", KEYWORDS),
+        ("relative", RELATIVE),
+        ("written", WRITTEN),
+        ("nested", NESTED),
+        ("words", WORDS),
+        ("unrecognized", UNRECOGNIZED),
+    ] {
+        group.throughput(Throughput::Elements(inputs.len() as u64));
+        group.bench_function(name, |b| {
+            b.iter(|| {
+                for input in inputs {
+                    let _ = black_box(parser.parse_relative_to(black_box(input), today));
+                }
+            })
+        });
+    }
+    group.finish();
+
+    // Includes reading the clock
+    c.bench_function("parse_date", |b| {
+        b.iter(|| parse_date(black_box("16 jan 2024")))
+    });
+}
+
+// The recognition step on its own: one pass over every rule's regex at once,
+// against trying each regex in turn as the parser used to
+fn bench_recognition(c: &mut Criterion) {
+    let inputs: Vec<String> = [
+        ISO,
+        KEYWORDS,
+        RELATIVE,
+        WRITTEN,
+        NESTED,
+        WORDS,
+        UNRECOGNIZED,
+    ]
+    .concat()
+    .iter()
+    .map(|input| input.to_lowercase())
+    .collect();
+
+    let mut group = c.benchmark_group("recognize");
+    group.throughput(Throughput::Elements(inputs.len() as u64));
+    type Recognize = fn(&str) -> Option<usize>;
+    let recognizers: [(&str, Recognize); 2] = [
+        ("single_pass", recognition::single_pass),
+        ("cascade", recognition::cascade),
+    ];
+    for (name, recognize) in recognizers {
+        group.bench_function(name, |b| {
+            b.iter(|| {
+                for input in &inputs {
+                    black_box(recognize(black_box(input)));
+                }
+            })
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bench_parse, bench_recognition);
+criterion_main!(benches);
@@ -0,0 +1,180 @@
+use anyhow::{anyhow, Result};
+use chrono::NaiveDate;
+use std::io::{Read, Write};
+
+use crate::{ParseContext, ParseError, Parser};
+
+/// Column of a CSV file holding dates.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Column {
+    /// Zero-based position of the column.
+    Index(usize),
+    /// Header of the column.
+    Name(String),
+}
+
+/// Rewrites a column of CSV or TSV records to ISO dates.
+///
+/// ```
+/// use temporis::{Column, Normalizer};
+///
+/// let input = "task,due\nreport,16/01/2024\nreview,jan-20\n";
+/// let mut output = Vec::new();
+/// let report = Normalizer::new(Column::Name("due".into()))
+///     .reference(chrono::NaiveDate::from_ymd_opt(2024, 1, 16).unwrap())
+///     .normalize(input.as_bytes(), &mut output)
+///     .unwrap();
+/// assert_eq!(report.converted, 2);
+/// assert_eq!(output, b"task,due\nreport,2024-01-16\nreview,2024-01-20\n");
+/// ```
+#[derive(Debug, Clone)]
+pub struct Normalizer {
+    column: Column,
+    delimiter: u8,
+    has_headers: bool,
+    reference: Option<NaiveDate>,
+    parser: Parser,
+}
+
+/// Value of a record that could not be parsed; it is written back unchanged.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RowError {
+    /// Line of the record in the input, starting at 1.
+    pub line: u64,
+    pub value: String,
+    pub error: ParseError,
+}
+
+/// Outcome of a normalization.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Report {
+    /// Records read, headers excluded.
+    pub rows: u64,
+    /// Values rewritten to ISO dates.
+    pub converted: u64,
+    pub errors: Vec<RowError>,
+}
+
+impl Normalizer {
+    pub fn new(column: Column) -> Self {
+        Normalizer {
+            column,
+            delimiter: b',',
+            has_headers: true,
+            reference: None,
+            parser: Parser::default(),
+        }
+    }
+
+    /// Sets the field delimiter, `b'\t'` for TSV (default `b','`).
+    pub fn delimiter(mut self, delimiter: u8) -> Self {
+        self.delimiter = delimiter;
+        self
+    }
+
+    /// Sets whether the first record is a header row, copied as is (default true).
+    pub fn has_headers(mut self, has_headers: bool) -> Self {
+        self.has_headers = has_headers;
+        self
+    }
+
+    /// Resolves relative values such as `tomorrow` against this date instead
+    /// of today.
+    pub fn reference(mut self, reference: NaiveDate) -> Self {
+        self.reference = Some(reference);
+        self
+    }
+
+    /// Sets the parser used for every value.
+    pub fn parser(mut self, parser: Parser) -> Self {
+        self.parser = parser;
+        self
+    }
+
+    /// Streams records from `input` to `output`, rewriting the column to ISO
+    /// dates. Empty values, values that fail to parse and records too short to
+    /// have the column are kept as is; the latter two are listed in the
+    /// report.
+    pub fn normalize<R: Read, W: Write>(&self, input: R, output: W) -> Result<Report> {
+        let mut reader = csv::ReaderBuilder::new()
+            .delimiter(self.delimiter)
+            .has_headers(self.has_headers)
+            .flexible(true)
+            .from_reader(input);
+        let mut writer = csv::WriterBuilder::new()
+            .delimiter(self.delimiter)
+            .flexible(true)
+            .from_writer(output);
+
+        let column = match &self.column {
+            Column::Index(index) => *index,
+            Column::Name(name) if self.has_headers => reader
+                .headers()?
+                .iter()
+                .position(|header| header == name)
+                .ok_or_else(|| anyhow!("No column named '{}'", name))?,
+            Column::Name(name) => {
+                return Err(anyhow!("Column '{}' needs a header row", name));
+            }
+        };
+        if self.has_headers {
+            writer.write_record(reader.headers()?)?;
+        }
+
+        // Every row shares one reference date, even across midnight
+        let mut context = ParseContext::new(self.parser.clone());
+        if let Some(reference) = self.reference {
+            context = context.reference(reference);
+        }
+        let mut report = Report::default();
+        let mut record = csv::StringRecord::new();
+        let mut normalized = csv::StringRecord::new();
+
+        while reader.read_record(&mut record)? {
+            report.rows += 1;
+            let line = record.position().map_or(0, |position| position.line());
+            // A short record is reported and copied like a failed value
+            let value = match record.get(column) {
+                Some(value) => value,
+                None => {
+                    report.errors.push(RowError {
+                        line,
+                        value: String::new(),
+                        error: ParseError::MissingField,
+                    });
+                    writer.write_record(&record)?;
+                    continue;
+                }
+            };
+            if value.trim().is_empty() {
+                writer.write_record(&record)?;
+                continue;
+            }
+
+            match context.parse(value) {
+                Ok(date) => {
+                    normalized.clear();
+                    for (index, field) in record.iter().enumerate() {
+                        if index == column {
+                            normalized.push_field(&date.format("%Y-%m-%d").to_string());
+                        } else {
+                            normalized.push_field(field);
+                        }
+                    }
+                    writer.write_record(&normalized)?;
+                    report.converted += 1;
+                }
+                Err(err) => {
+                    report.errors.push(RowError {
+                        line,
+                        value: value.to_string(),
+                        error: ParseError::from_anyhow(err),
+                    });
+                    writer.write_record(&record)?;
+                }
+            }
+        }
+        writer.flush()?;
+        Ok(report)
+    }
+}
@@ -0,0 +1,191 @@
+//! Value parsers for date arguments of clap commands.
+//!
+//! ```
+//! use chrono::NaiveDate;
+//! use clap::Parser;
+//! use temporis::NaturalDate;
+//!
+//! #[derive(Parser)]
+//! struct Args {
+//!     #[arg(long, value_parser = temporis::clap::date())]
+//!     due: NaiveDate,
+//!     // `NaturalDate`, `NaturalRange` and `NaturalDateTime` need no value parser
+//!     #[arg(long)]
+//!     wait: Option<NaturalDate>,
+//! }
+//!
+//! let args = Args::parse_from(["tool", "--due", "16 jan 2024", "--wait", "eom"]);
+//! assert_eq!(args.due, NaiveDate::from_ymd_opt(2024, 1, 16).unwrap());
+//!
+//! let err = Args::try_parse_from(["tool", "--due", "tomorow"]).err().unwrap();
+//! assert!(err.to_string().contains("tip: a similar value exists: 'tomorrow'"));
+//! ```
+
+use ::clap::builder::{TypedValueParser, ValueParserFactory};
+use ::clap::error::{ContextKind, ContextValue};
+use ::clap::{Arg, Command};
+use chrono::{NaiveDate, NaiveDateTime};
+use std::ffi::OsStr;
+use std::marker::PhantomData;
+
+use crate::{DateRange, NaturalDate, NaturalDateTime, NaturalRange, ParseError, Parser};
+
+/// Values a date argument can be parsed into.
+pub trait FromExpression: Sized + Clone + Send + Sync + 'static {
+    fn from_expression(parser: &Parser, expr: &str) -> Result<Self, anyhow::Error>;
+}
+
+impl FromExpression for NaiveDate {
+    fn from_expression(parser: &Parser, expr: &str) -> Result<Self, anyhow::Error> {
+        parser.parse(expr)
+    }
+}
+
+impl FromExpression for DateRange {
+    fn from_expression(parser: &Parser, expr: &str) -> Result<Self, anyhow::Error> {
+        parser.parse_range(expr)
+    }
+}
+
+impl FromExpression for NaiveDateTime {
+    fn from_expression(parser: &Parser, expr: &str) -> Result<Self, anyhow::Error> {
+        parser.parse_datetime(expr)
+    }
+}
+
+impl FromExpression for NaturalDate {
+    fn from_expression(parser: &Parser, expr: &str) -> Result<Self, anyhow::Error> {
+        Ok(NaturalDate::new(expr.trim(), parser.parse(expr)?))
+    }
+}
+
+impl FromExpression for NaturalRange {
+    fn from_expression(parser: &Parser, expr: &str) -> Result<Self, anyhow::Error> {
+        Ok(NaturalRange::new(expr.trim(), parser.parse_range(expr)?))
+    }
+}
+
+impl FromExpression for NaturalDateTime {
+    fn from_expression(parser: &Parser, expr: &str) -> Result<Self, anyhow::Error> {
+        Ok(NaturalDateTime::new(
+            expr.trim(),
+            parser.parse_datetime(expr)?,
+        ))
+    }
+}
+
+/// Parses arguments into `T`, reporting the parser's suggestions as clap tips.
+#[derive(Debug)]
+pub struct NaturalValueParser<T> {
+    parser: Parser,
+    marker: PhantomData<fn() -> T>,
+}
+
+impl<T> NaturalValueParser<T> {
+    pub fn new() -> Self {
+        NaturalValueParser {
+            parser: Parser::default(),
+            marker: PhantomData,
+        }
+    }
+
+    /// Sets the parser used for the argument.
+    pub fn parser(mut self, parser: Parser) -> Self {
+        self.parser = parser;
+        self
+    }
+}
+
+impl<T> Default for NaturalValueParser<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> Clone for NaturalValueParser<T> {
+    fn clone(&self) -> Self {
+        NaturalValueParser {
+            parser: self.parser.clone(),
+            marker: PhantomData,
+        }
+    }
+}
+
+impl<T: FromExpression> TypedValueParser for NaturalValueParser<T> {
+    type Value = T;
+
+    fn parse_ref(
+        &self,
+        cmd: &Command,
+        arg: Option<&Arg>,
+        value: &OsStr,
+    ) -> Result<Self::Value, ::clap::Error> {
+        let (message, suggestions) = match value.to_str() {
+            Some(expr) => match T::from_expression(&self.parser, expr) {
+                Ok(value) => return Ok(value),
+                Err(err) => match err.downcast::<ParseError>() {
+                    // Suggestions go to clap's tip instead of the message
+                    Ok(ParseError::Unrecognized { suggestions }) => {
+                        (ParseError::unrecognized().to_string(), suggestions)
+                    }
+                    Ok(err) => (err.to_string(), Vec::new()),
+                    Err(err) => (err.to_string(), Vec::new()),
+                },
+            },
+            None => (String::new(), Vec::new()),
+        };
+
+        // Let clap build its usual error, then attach the suggestions
+        let fail = move |_: &str| Err::<T, String>(message.clone());
+        let mut err = match fail.parse_ref(cmd, arg, value) {
+            Ok(value) => return Ok(value),
+            Err(err) => err,
+        };
+        if !suggestions.is_empty() {
+            err.insert(
+                ContextKind::SuggestedValue,
+                ContextValue::Strings(suggestions),
+            );
+        }
+        Err(err)
+    }
+}
+
+/// Parser for `NaiveDate` arguments.
+pub fn date() -> NaturalValueParser<NaiveDate> {
+    NaturalValueParser::new()
+}
+
+/// Parser for `DateRange` arguments.
+pub fn range() -> NaturalValueParser<DateRange> {
+    NaturalValueParser::new()
+}
+
+/// Parser for `NaiveDateTime` arguments.
+pub fn datetime() -> NaturalValueParser<NaiveDateTime> {
+    NaturalValueParser::new()
+}
+
+impl ValueParserFactory for NaturalDate {
+    type Parser = NaturalValueParser<NaturalDate>;
+
+    fn value_parser() -> Self::Parser {
+        NaturalValueParser::new()
+    }
+}
+
+impl ValueParserFactory for NaturalRange {
+    type Parser = NaturalValueParser<NaturalRange>;
+
+    fn value_parser() -> Self::Parser {
+        NaturalValueParser::new()
+    }
+}
+
+impl ValueParserFactory for NaturalDateTime {
+    type Parser = NaturalValueParser<NaturalDateTime>;
+
+    fn value_parser() -> Self::Parser {
+        NaturalValueParser::new()
+    }
+}
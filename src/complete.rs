@@ -0,0 +1,145 @@
+use anyhow::anyhow;
+use chrono::{Local, NaiveDate};
+
+use crate::{taskwarrior, Parser, KEYWORDS};
+
+/// Expression offered for a prefix, with the date it resolves to.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Completion {
+    pub text: String,
+    pub date: NaiveDate,
+}
+
+/// Shells `completion_script` can target.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Shell {
+    Bash,
+    Zsh,
+    Fish,
+}
+
+/// Endings offered after a number typed on its own (`3` -> `3d`, `3rd`, `3mon`).
+const NUMBER_SUFFIXES: &[&str] = &[
+    "d", "w", "m", "y", "mon", "tue", "wed", "thu", "fri", "sat", "sun",
+];
+
+/// Longer expressions offered once their first word is typed; each must
+/// parse, or completion drops it.
+const PHRASES: &[&str] = &[
+    "first day of next month",
+    "last day of month",
+    "last day of next month",
+    "penultimate day of month",
+    "last friday of month",
+    "first monday of next month",
+];
+
+impl Parser {
+    /// Lists every keyword and template starting with `prefix`, with the date
+    /// each resolves to today.
+    pub fn complete(&self, prefix: &str) -> Vec<Completion> {
+        self.complete_relative_to(prefix, Local::now().date_naive())
+    }
+
+    /// Lists every keyword and template starting with `prefix`, with the date
+    /// each resolves to relative to the given reference date.
+    pub fn complete_relative_to(&self, prefix: &str, today: NaiveDate) -> Vec<Completion> {
+        let prefix = prefix.trim_start().to_lowercase();
+        let mut candidates: Vec<String> = Vec::new();
+
+        // The prefix itself when it is already complete (2024, 3days)
+        candidates.push(prefix.clone());
+
+        let unsigned = prefix.strip_prefix('-').unwrap_or(&prefix);
+        let digits = &unsigned[..unsigned
+            .find(|c: char| !c.is_ascii_digit())
+            .unwrap_or(unsigned.len())];
+        if !digits.is_empty() {
+            let number = &prefix[..prefix.len() - unsigned.len() + digits.len()];
+            let ordinal = format!("{}{}", number, ordinal_suffix(digits));
+            candidates.extend(
+                NUMBER_SUFFIXES
+                    .iter()
+                    .map(|suffix| format!("{}{}", number, suffix))
+                    .chain(std::iter::once(ordinal)),
+            );
+        }
+
+        candidates.extend(KEYWORDS.iter().chain(PHRASES).map(|word| word.to_string()));
+        candidates.extend(self.keywords.names().map(str::to_string));
+        if self.taskwarrior {
+            candidates.extend(taskwarrior::NAMED_DATES.iter().map(|word| word.to_string()));
+        }
+
+        let mut completions: Vec<Completion> = Vec::new();
+        for text in candidates {
+            if text.is_empty()
+                || !text.starts_with(&prefix)
+                || completions.iter().any(|completion| completion.text == text)
+            {
+                continue;
+            }
+            if let Ok(date) = self.resolve(&text, today) {
+                completions.push(Completion { text, date });
+            }
+        }
+        completions
+    }
+}
+
+/// Lists every keyword and template starting with `prefix`.
+pub fn complete(prefix: &str) -> Vec<Completion> {
+    Parser::default().complete(prefix)
+}
+
+/// Generates a script completing the date arguments of `command` in `shell`
+/// with every keyword `complete` offers. The command name may hold only
+/// ASCII letters, digits, `_` and `-`, so that it needs no quoting.
+pub fn completion_script(shell: Shell, command: &str) -> Result<String, anyhow::Error> {
+    let valid = !command.is_empty()
+        && command
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-');
+    if !valid {
+        return Err(anyhow!("Invalid command name '{}'", command));
+    }
+    let words: Vec<String> = complete("")
+        .into_iter()
+        .map(|completion| completion.text)
+        .filter(|text| !text.contains(' '))
+        .collect();
+    let words = words.join(" ");
+    let function: String = command
+        .chars()
+        .map(|c| if c == '-' { '_' } else { c })
+        .collect();
+
+    let script = match shell {
+        Shell::Bash => format!(
+            "_{function}_dates() {{\n    \
+                 local cur=\"${{COMP_WORDS[COMP_CWORD]}}\"\n    \
+                 COMPREPLY=($(compgen -W \"{words}\" -- \"$cur\"))\n\
+             }}\n\
+             complete -F _{function}_dates {command}\n"
+        ),
+        Shell::Zsh => format!(
+            "#compdef {command}\n\n\
+             _{function}_dates() {{\n    \
+                 compadd -- {words}\n\
+             }}\n\n\
+             compdef _{function}_dates {command}\n"
+        ),
+        Shell::Fish => format!("complete -c {command} -f -a \"{words}\"\n"),
+    };
+    Ok(script)
+}
+
+pub(crate) fn ordinal_suffix(digits: &str) -> &'static str {
+    let tens = digits.len() >= 2 && digits.as_bytes()[digits.len() - 2] == b'1';
+    match digits.as_bytes().last() {
+        Some(b'1') if !tens => "st",
+        Some(b'2') if !tens => "nd",
+        Some(b'3') if !tens => "rd",
+        _ => "th",
+    }
+}
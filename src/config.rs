@@ -0,0 +1,168 @@
+use anyhow::{anyhow, Context};
+use chrono::NaiveDate;
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::fmt::Display;
+use std::ops::Range;
+use std::path::Path;
+use toml::{Spanned, Value};
+
+use crate::{Anchor, KeywordRegistry, Locale, Parser};
+
+/// Parser settings shared through a TOML file, so that every tool reads dates
+/// the same way. Options left out keep the parser's defaults.
+///
+/// ```
+/// let config = temporis::ParserConfig::from_toml(r#"
+///     locale = "fr"
+///     two_digit_year_window = 20
+///     anchor = "end"
+///
+///     [keywords]
+///     release = 2024-03-01
+///     freeze = "release - 1w"
+/// "#).unwrap();
+/// let date = config.parser().parse("freeze").unwrap();
+/// assert_eq!(date, chrono::NaiveDate::from_ymd_opt(2024, 2, 23).unwrap());
+///
+/// let err = temporis::ParserConfig::from_toml("strict = true\neom = 2024-03-01\n").unwrap_err();
+/// assert!(err.to_string().starts_with("line 2: "));
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct ParserConfig {
+    pub locale: Option<Locale>,
+    pub strict: Option<bool>,
+    pub autocorrect: Option<bool>,
+    pub two_digit_year_window: Option<u32>,
+    pub anchor: Option<Anchor>,
+    pub clamp_days: Option<bool>,
+    pub taskwarrior: Option<bool>,
+    pub couple: Option<u32>,
+    pub few: Option<u32>,
+    /// Keywords of the `[keywords]` table: TOML dates are fixed dates and
+    /// strings are expressions.
+    pub keywords: KeywordRegistry,
+}
+
+/// Layout of the file, before validation.
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct File {
+    locale: Option<Spanned<String>>,
+    strict: Option<bool>,
+    autocorrect: Option<bool>,
+    two_digit_year_window: Option<Spanned<u32>>,
+    anchor: Option<Spanned<String>>,
+    clamp_days: Option<bool>,
+    taskwarrior: Option<bool>,
+    couple: Option<u32>,
+    few: Option<u32>,
+    #[serde(default)]
+    keywords: BTreeMap<String, Spanned<Value>>,
+}
+
+impl ParserConfig {
+    /// Reads the configuration in `path`; errors name the file and the line.
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, anyhow::Error> {
+        let path = path.as_ref();
+        let source = std::fs::read_to_string(path)
+            .with_context(|| format!("Cannot read {}", path.display()))?;
+        Self::from_toml(&source).with_context(|| path.display().to_string())
+    }
+
+    /// Parses a configuration; errors start with the offending line.
+    pub fn from_toml(source: &str) -> Result<Self, anyhow::Error> {
+        let file: File = toml::from_str(source).map_err(|err| match err.span() {
+            Some(span) => at(source, span, err.message()),
+            None => anyhow!("{}", err.message()),
+        })?;
+
+        let mut config = ParserConfig {
+            strict: file.strict,
+            autocorrect: file.autocorrect,
+            clamp_days: file.clamp_days,
+            taskwarrior: file.taskwarrior,
+            couple: file.couple,
+            few: file.few,
+            ..Default::default()
+        };
+        if let Some(locale) = file.locale {
+            let parsed = locale.get_ref().parse();
+            config.locale = Some(parsed.map_err(|err| at(source, locale.span(), err))?);
+        }
+        if let Some(years) = file.two_digit_year_window {
+            if *years.get_ref() > 99 {
+                return Err(at(source, years.span(), "Window must be at most 99 years"));
+            }
+            config.two_digit_year_window = Some(years.into_inner());
+        }
+        if let Some(anchor) = file.anchor {
+            config.anchor = Some(match anchor.get_ref().as_str() {
+                "start" => Anchor::Start,
+                "end" => Anchor::End,
+                other => {
+                    let message = format!("Unknown anchor '{}', expected start or end", other);
+                    return Err(at(source, anchor.span(), message));
+                }
+            });
+        }
+
+        // In file order, so that aliases can use the keywords above them, and
+        // checked with the settings of the file
+        let mut keywords: Vec<_> = file.keywords.into_iter().collect();
+        keywords.sort_by_key(|(_, value)| value.span().start);
+        for (name, value) in keywords {
+            let span = value.span();
+            let registered = match value.into_inner() {
+                Value::String(expr) => {
+                    let parser = config.parser();
+                    config.keywords.register_alias_with(&parser, &name, &expr)
+                }
+                Value::Datetime(datetime) => match (datetime.date, datetime.time) {
+                    (Some(date), None) => NaiveDate::from_ymd_opt(
+                        date.year.into(),
+                        date.month.into(),
+                        date.day.into(),
+                    )
+                    .ok_or_else(|| anyhow!("Invalid date"))
+                    .and_then(|date| {
+                        let parser = config.parser();
+                        config.keywords.register_date_with(&parser, &name, date)
+                    }),
+                    _ => Err(anyhow!("Keyword '{}' must be a date without a time", name)),
+                },
+                _ => Err(anyhow!(
+                    "Keyword '{}' must be a date or an expression",
+                    name
+                )),
+            };
+            registered.map_err(|err| at(source, span, err))?;
+        }
+        Ok(config)
+    }
+
+    /// Parser with these settings.
+    pub fn parser(&self) -> Parser {
+        let defaults = Parser::default();
+        let mut parser = Parser::new()
+            .locale(self.locale.unwrap_or(defaults.locale))
+            .strict(self.strict.unwrap_or(defaults.strict))
+            .autocorrect(self.autocorrect.unwrap_or(defaults.autocorrect))
+            .anchor(self.anchor.unwrap_or(defaults.anchor))
+            .clamp_days(self.clamp_days.unwrap_or(defaults.clamp_days))
+            .taskwarrior(self.taskwarrior.unwrap_or(defaults.taskwarrior))
+            .couple(self.couple.unwrap_or(defaults.couple))
+            .few(self.few.unwrap_or(defaults.few))
+            .keywords(self.keywords.clone());
+        if let Some(years) = self.two_digit_year_window {
+            parser = parser.two_digit_year_window(years);
+        }
+        parser
+    }
+}
+
+/// Error located at the line of `span` in `source`.
+fn at(source: &str, span: Range<usize>, message: impl Display) -> anyhow::Error {
+    let line = source[..span.start.min(source.len())].matches('\n').count() + 1;
+    anyhow!("line {}: {}", line, message)
+}
@@ -0,0 +1,109 @@
+use chrono::{Local, NaiveDate, NaiveDateTime, NaiveTime};
+use std::collections::HashMap;
+
+use crate::{DateRange, Explanation, ParseError, Parser};
+
+/// Distinct expressions remembered by a context; batches repeat the same few
+/// dates over and over.
+const CACHE_SIZE: usize = 1 << 16;
+
+/// Parser bound to a fixed reference instant, for parsing many expressions
+/// consistently: every `today` of a batch is the same day, even when the batch
+/// runs across midnight, and repeated expressions are only resolved once.
+///
+/// ```
+/// use chrono::NaiveDate;
+/// use temporis::{ParseContext, Parser};
+///
+/// let today = NaiveDate::from_ymd_opt(2024, 1, 16).unwrap();
+/// let mut context = ParseContext::new(Parser::new()).reference(today);
+/// let dates: Vec<_> = context
+///     .parse_many(["today", "eom", "today", "xyz"])
+///     .collect();
+/// assert_eq!(dates[0].as_ref().unwrap(), &today);
+/// assert_eq!(dates[1].as_ref().unwrap(), &NaiveDate::from_ymd_opt(2024, 1, 31).unwrap());
+/// assert!(dates[3].is_err());
+/// ```
+#[derive(Debug, Clone)]
+pub struct ParseContext {
+    parser: Parser,
+    now: NaiveDateTime,
+    cache: HashMap<String, Result<NaiveDate, ParseError>>,
+}
+
+impl ParseContext {
+    /// Freezes the parser's options and the current local time.
+    pub fn new(parser: Parser) -> Self {
+        ParseContext {
+            parser,
+            now: Local::now().naive_local(),
+            cache: HashMap::new(),
+        }
+    }
+
+    /// Resolves expressions against this date, at midnight, instead of now.
+    pub fn reference(self, today: NaiveDate) -> Self {
+        self.now(today.and_time(NaiveTime::MIN))
+    }
+
+    /// Resolves expressions against this instant instead of now.
+    pub fn now(mut self, now: NaiveDateTime) -> Self {
+        self.now = now;
+        self.cache.clear();
+        self
+    }
+
+    /// Date expressions are resolved against.
+    pub fn today(&self) -> NaiveDate {
+        self.now.date()
+    }
+
+    /// Parses a date, answering repeated expressions from memory.
+    pub fn parse(&mut self, date_str: &str) -> Result<NaiveDate, anyhow::Error> {
+        if let Some(result) = self.cache.get(date_str) {
+            return result.clone().map_err(Into::into);
+        }
+        let result = self.parser.parse_relative_to(date_str, self.today());
+        // Only a `ParseError` can be kept; any other error is returned as is
+        let cached = match &result {
+            Ok(date) => Ok(*date),
+            Err(err) => match err.downcast_ref::<ParseError>() {
+                Some(err) => Err(err.clone()),
+                None => return result,
+            },
+        };
+        if self.cache.len() >= CACHE_SIZE {
+            self.cache.clear();
+        }
+        self.cache.insert(date_str.to_string(), cached);
+        result
+    }
+
+    /// Parses every expression of `exprs`, in order.
+    pub fn parse_many<'a, I>(
+        &'a mut self,
+        exprs: I,
+    ) -> impl Iterator<Item = Result<NaiveDate, anyhow::Error>> + 'a
+    where
+        I: IntoIterator,
+        I::Item: AsRef<str>,
+        I::IntoIter: 'a,
+    {
+        exprs.into_iter().map(move |expr| self.parse(expr.as_ref()))
+    }
+
+    /// Parses an expression into the span of days it covers.
+    pub fn parse_range(&self, date_str: &str) -> Result<DateRange, anyhow::Error> {
+        self.parser.parse_range_relative_to(date_str, self.today())
+    }
+
+    /// Parses an expression followed by an optional time of day.
+    pub fn parse_datetime(&self, date_str: &str) -> Result<NaiveDateTime, anyhow::Error> {
+        self.parser.parse_datetime_relative_to(date_str, self.now)
+    }
+
+    /// Explains how an expression resolves.
+    pub fn explain(&self, date_str: &str) -> Explanation {
+        self.parser.explain_relative_to(date_str, self.today())
+    }
+}
@@ -0,0 +1,434 @@
+use chrono::{Duration, Months, NaiveDate, NaiveDateTime};
+use lazy_static::lazy_static;
+use regex::Regex;
+use std::cmp::Ordering;
+use std::fmt;
+use std::ops::Neg;
+use std::str::FromStr;
+
+use crate::{normalize, ParseError, Parser};
+
+lazy_static! {
+    // An amount and its unit, such as `3 weeks` or `1.5d`
+    static ref GROUP_REGEX: Regex = Regex::new(r"^(\d+(?:\.\d+)?)\s*([a-z]+)").unwrap();
+    // A designator of ISO-8601, such as `2M` in `P1Y2M`
+    static ref ISO_GROUP_REGEX: Regex = Regex::new(r"^(\d+(?:\.\d+)?)([a-z])").unwrap();
+    // A separator between groups: spaces, a comma or `and`
+    static ref SEPARATOR_REGEX: Regex = Regex::new(r"^(?:\s*,\s*|\s+and\s+|\s*)").unwrap();
+}
+
+const SECONDS_PER_DAY: i64 = 86_400;
+const NANOSECONDS_PER_SECOND: i64 = 1_000_000_000;
+
+/// Length of time that keeps the calendar meaning of its units: months and
+/// years move the month (January 31st plus `1m` is the last day of February)
+/// and days move the date, whatever their length in seconds.
+///
+/// Parsed from units (`1w2d`, `3 weeks`, `1.5d`, `2h30min`) or ISO-8601
+/// (`P1M2D`, `PT36H`). Weeks count as 7 days; fractions carry into the next
+/// smaller unit, a month counting 30 days. A leading `-` negates the whole.
+///
+/// ```
+/// use chrono::NaiveDate;
+/// use temporis::{parse_duration, CalendarDuration};
+///
+/// let estimate = parse_duration("1w2d").unwrap();
+/// assert_eq!(estimate.days(), 9);
+/// assert_eq!(estimate.to_string(), "1w2d");
+/// assert!(estimate < parse_duration("2 weeks").unwrap());
+///
+/// let start = NaiveDate::from_ymd_opt(2024, 1, 31).unwrap();
+/// let month: CalendarDuration = "P1M".parse().unwrap();
+/// assert_eq!(month.add_to(start), NaiveDate::from_ymd_opt(2024, 2, 29));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct CalendarDuration {
+    months: i64,
+    days: i64,
+    /// Below a day, with the sign of `days`.
+    seconds: i64,
+    /// Below a second, with the sign of `seconds`.
+    nanoseconds: i64,
+}
+
+/// Unit of a duration, largest first.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub(crate) enum Unit {
+    Year,
+    Month,
+    Week,
+    Day,
+    Hour,
+    Minute,
+    Second,
+}
+
+impl Unit {
+    const ALL: [Unit; 7] = [
+        Unit::Year,
+        Unit::Month,
+        Unit::Week,
+        Unit::Day,
+        Unit::Hour,
+        Unit::Minute,
+        Unit::Second,
+    ];
+
+    /// Units a date offset such as `eom+1w2d` may use.
+    pub(crate) const DATE: [Unit; 4] = [Unit::Year, Unit::Month, Unit::Week, Unit::Day];
+
+    pub(crate) fn parse(name: &str) -> Option<Unit> {
+        Unit::ALL
+            .into_iter()
+            .find(|unit| unit.names().contains(&name))
+    }
+
+    /// Every way of writing the unit, such as `w` or `weeks`.
+    pub(crate) fn names(self) -> &'static [&'static str] {
+        match self {
+            Unit::Year => &["y", "yr", "yrs", "year", "years"],
+            Unit::Month => &["m", "mth", "mths", "month", "months"],
+            Unit::Week => &["w", "wk", "wks", "week", "weeks"],
+            Unit::Day => &["d", "day", "days"],
+            Unit::Hour => &["h", "hr", "hrs", "hour", "hours"],
+            Unit::Minute => &["min", "mins", "minute", "minutes"],
+            Unit::Second => &["s", "sec", "secs", "second", "seconds"],
+        }
+    }
+
+    pub(crate) fn name(self) -> &'static str {
+        match self {
+            Unit::Year => "year",
+            Unit::Month => "month",
+            Unit::Week => "week",
+            Unit::Day => "day",
+            Unit::Hour => "hour",
+            Unit::Minute => "minute",
+            Unit::Second => "second",
+        }
+    }
+
+    /// Smaller units a fraction of this one carries into, with how many of
+    /// each the previous one holds.
+    fn carries(self) -> &'static [(Unit, i64)] {
+        match self {
+            Unit::Year => &[
+                (Unit::Month, 12),
+                (Unit::Day, 30),
+                (Unit::Second, SECONDS_PER_DAY),
+            ],
+            Unit::Month => &[(Unit::Day, 30), (Unit::Second, SECONDS_PER_DAY)],
+            Unit::Week => &[(Unit::Day, 7), (Unit::Second, SECONDS_PER_DAY)],
+            Unit::Day => &[(Unit::Second, SECONDS_PER_DAY)],
+            Unit::Hour => &[(Unit::Second, 3_600)],
+            Unit::Minute => &[(Unit::Second, 60)],
+            Unit::Second => &[],
+        }
+    }
+}
+
+impl CalendarDuration {
+    /// Whole years.
+    pub fn years(&self) -> i64 {
+        self.months / 12
+    }
+
+    /// Months after the whole years.
+    pub fn months(&self) -> i64 {
+        self.months % 12
+    }
+
+    /// Whole days, weeks included.
+    pub fn days(&self) -> i64 {
+        self.days
+    }
+
+    /// Seconds after the whole days.
+    pub fn seconds(&self) -> i64 {
+        self.seconds
+    }
+
+    /// Nanoseconds after the whole seconds.
+    pub fn nanoseconds(&self) -> i64 {
+        self.nanoseconds
+    }
+
+    pub fn is_zero(&self) -> bool {
+        *self == CalendarDuration::default()
+    }
+
+    /// Adds the duration to `date`, months first and then days; time below a
+    /// day is left out. `None` when the result is out of range.
+    pub fn add_to(&self, date: NaiveDate) -> Option<NaiveDate> {
+        add_months(date, self.months)?.checked_add_signed(Duration::try_days(self.days)?)
+    }
+
+    /// Adds the duration to `datetime`, months first, then days, then the
+    /// time below a day. `None` when the result is out of range.
+    pub fn add_to_datetime(&self, datetime: NaiveDateTime) -> Option<NaiveDateTime> {
+        self.add_to(datetime.date())?
+            .and_time(datetime.time())
+            .checked_add_signed(Duration::try_seconds(self.seconds)?)?
+            .checked_add_signed(Duration::nanoseconds(self.nanoseconds))
+    }
+
+    fn total_nanoseconds(&self) -> i128 {
+        let seconds =
+            i128::from(self.days) * i128::from(SECONDS_PER_DAY) + i128::from(self.seconds);
+        seconds * i128::from(NANOSECONDS_PER_SECOND) + i128::from(self.nanoseconds)
+    }
+
+    /// Duration of `months`, `days`, `seconds` and `nanoseconds` of the same
+    /// sign, each carried into the next larger unit.
+    fn new(months: i64, days: i64, seconds: i64, nanoseconds: i64) -> Option<Self> {
+        let seconds = seconds.checked_add(nanoseconds / NANOSECONDS_PER_SECOND)?;
+        Some(CalendarDuration {
+            months,
+            days: days.checked_add(seconds / SECONDS_PER_DAY)?,
+            seconds: seconds % SECONDS_PER_DAY,
+            nanoseconds: nanoseconds % NANOSECONDS_PER_SECOND,
+        })
+    }
+}
+
+/// Shifts by whole months, keeping the day of the month where it exists and
+/// taking the last day of the month otherwise.
+fn add_months(date: NaiveDate, months: i64) -> Option<NaiveDate> {
+    let shift = Months::new(u32::try_from(months.unsigned_abs()).ok()?);
+    if months < 0 {
+        date.checked_sub_months(shift)
+    } else {
+        date.checked_add_months(shift)
+    }
+}
+
+/// Durations compare when one is at least as long in both months and time:
+/// `1w2d < 2w`, but `1m` and `30d` are unordered.
+impl PartialOrd for CalendarDuration {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        let months = self.months.cmp(&other.months);
+        let time = self.total_nanoseconds().cmp(&other.total_nanoseconds());
+        match (months, time) {
+            (ordering, Ordering::Equal) | (Ordering::Equal, ordering) => Some(ordering),
+            (months, time) if months == time => Some(months),
+            _ => None,
+        }
+    }
+}
+
+impl Neg for CalendarDuration {
+    type Output = CalendarDuration;
+
+    fn neg(self) -> Self::Output {
+        CalendarDuration {
+            months: -self.months,
+            days: -self.days,
+            seconds: -self.seconds,
+            nanoseconds: -self.nanoseconds,
+        }
+    }
+}
+
+/// Writes the duration in units, largest first (`1y2m`, `1w2d`, `1d12h`),
+/// in a form `parse_duration` reads back.
+impl fmt::Display for CalendarDuration {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_zero() {
+            return write!(f, "0d");
+        }
+        let negative = self.months < 0 || self.days < 0 || self.seconds < 0 || self.nanoseconds < 0;
+        let duration = if negative { -*self } else { *self };
+        if negative {
+            write!(f, "-")?;
+        }
+        let groups = [
+            (duration.years(), "y"),
+            (duration.months(), "m"),
+            (duration.days / 7, "w"),
+            (duration.days % 7, "d"),
+            (duration.seconds / 3_600, "h"),
+            (duration.seconds % 3_600 / 60, "min"),
+        ];
+        for (amount, unit) in groups {
+            if amount != 0 {
+                write!(f, "{}{}", amount, unit)?;
+            }
+        }
+        let seconds = duration.seconds % 60;
+        match duration.nanoseconds {
+            0 if seconds == 0 => Ok(()),
+            0 => write!(f, "{}s", seconds),
+            nanoseconds => {
+                let fraction = format!("{:09}", nanoseconds);
+                write!(f, "{}.{}s", seconds, fraction.trim_end_matches('0'))
+            }
+        }
+    }
+}
+
+impl FromStr for CalendarDuration {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        parse_duration(s)
+    }
+}
+
+/// Totals of the groups read so far, rejecting a unit given twice.
+#[derive(Default)]
+struct Sum {
+    months: i64,
+    days: i64,
+    seconds: i64,
+    nanoseconds: i64,
+    seen: Vec<Unit>,
+}
+
+impl Sum {
+    /// Adds `amount` (digits, optionally with a fraction) of `unit`.
+    fn add(&mut self, amount: &str, unit: Unit) -> Result<(), anyhow::Error> {
+        if self.seen.contains(&unit) {
+            return Err(ParseError::RepeatedUnit(unit.name()).into());
+        }
+        self.seen.push(unit);
+
+        let (whole, fraction) = amount.split_once('.').unwrap_or((amount, ""));
+        let whole: i64 = whole.parse().map_err(|_| ParseError::OutOfRange)?;
+        self.add_whole(whole, unit)?;
+
+        // Carry the fraction down the smaller units, ending in nanoseconds
+        if fraction.len() > 9 {
+            return Err(ParseError::OutOfRange.into());
+        }
+        let denominator = 10_i64.pow(fraction.len() as u32);
+        let mut numerator: i64 = fraction.parse().unwrap_or(0);
+        for &(smaller, factor) in unit.carries() {
+            let carried = numerator * factor;
+            self.add_whole(carried / denominator, smaller)?;
+            numerator = carried % denominator;
+        }
+        self.nanoseconds += numerator * NANOSECONDS_PER_SECOND / denominator;
+        Ok(())
+    }
+
+    fn add_whole(&mut self, amount: i64, unit: Unit) -> Result<(), anyhow::Error> {
+        let (total, factor) = match unit {
+            Unit::Year => (&mut self.months, 12),
+            Unit::Month => (&mut self.months, 1),
+            Unit::Week => (&mut self.days, 7),
+            Unit::Day => (&mut self.days, 1),
+            Unit::Hour => (&mut self.seconds, 3_600),
+            Unit::Minute => (&mut self.seconds, 60),
+            Unit::Second => (&mut self.seconds, 1),
+        };
+        *total = amount
+            .checked_mul(factor)
+            .and_then(|amount| total.checked_add(amount))
+            .ok_or(ParseError::OutOfRange)?;
+        Ok(())
+    }
+
+    fn finish(self, negative: bool) -> Result<CalendarDuration, anyhow::Error> {
+        if self.seen.is_empty() {
+            return Err(ParseError::UnrecognizedDuration.into());
+        }
+        let duration =
+            CalendarDuration::new(self.months, self.days, self.seconds, self.nanoseconds)
+                .ok_or(ParseError::OutOfRange)?;
+        Ok(if negative { -duration } else { duration })
+    }
+}
+
+/// Reads a normalized duration in units or ISO-8601.
+pub(crate) fn parse(input: &str) -> Result<CalendarDuration, anyhow::Error> {
+    let (negative, rest) = match input.strip_prefix('-') {
+        Some(rest) => (true, rest),
+        None => (false, input.strip_prefix('+').unwrap_or(input)),
+    };
+    match rest.strip_prefix('p') {
+        Some(iso) => parse_iso(iso)?.finish(negative),
+        None => sum(&groups(rest)?, negative),
+    }
+}
+
+/// Amounts and units of `1w2d`, `1 week 2 days` or `1 week and 2 days`.
+pub(crate) fn groups(mut input: &str) -> Result<Vec<(&str, &str)>, anyhow::Error> {
+    let mut groups = Vec::new();
+    while !input.is_empty() {
+        let caps = GROUP_REGEX
+            .captures(input)
+            .ok_or(ParseError::UnrecognizedDuration)?;
+        let (amount, unit) = (caps.get(1).unwrap(), caps.get(2).unwrap());
+        groups.push((amount.as_str(), unit.as_str()));
+        input = &input[unit.end()..];
+        let separator = SEPARATOR_REGEX.find(input).map_or(0, |m| m.end());
+        if separator == input.len() && separator > 0 {
+            return Err(ParseError::UnrecognizedDuration.into());
+        }
+        input = &input[separator..];
+    }
+    Ok(groups)
+}
+
+/// Total of `groups`, negated when `negative`; a unit may appear only once.
+pub(crate) fn sum(
+    groups: &[(&str, &str)],
+    negative: bool,
+) -> Result<CalendarDuration, anyhow::Error> {
+    let mut sum = Sum::default();
+    for (amount, unit) in groups {
+        sum.add(
+            amount,
+            Unit::parse(unit).ok_or(ParseError::UnrecognizedDuration)?,
+        )?;
+    }
+    sum.finish(negative)
+}
+
+/// Reads what follows the `P` of an ISO-8601 duration: `1Y2M3W4D`, then
+/// `T5H6M7S`, each designator at most once and in that order.
+fn parse_iso(input: &str) -> Result<Sum, anyhow::Error> {
+    let (date, time) = match input.split_once('t') {
+        Some((date, time)) if !time.is_empty() => (date, time),
+        Some(_) => return Err(ParseError::UnrecognizedDuration.into()),
+        None => (input, ""),
+    };
+
+    let mut sum = Sum::default();
+    for (mut part, section) in [(date, 0), (time, 1)] {
+        while !part.is_empty() {
+            let caps = ISO_GROUP_REGEX
+                .captures(part)
+                .ok_or(ParseError::UnrecognizedDuration)?;
+            let unit = match (section, &caps[2]) {
+                (0, "y") => Unit::Year,
+                (0, "m") => Unit::Month,
+                (0, "w") => Unit::Week,
+                (0, "d") => Unit::Day,
+                (1, "h") => Unit::Hour,
+                (1, "m") => Unit::Minute,
+                (1, "s") => Unit::Second,
+                _ => return Err(ParseError::UnrecognizedDuration.into()),
+            };
+            if sum.seen.last().is_some_and(|last| *last >= unit) {
+                return Err(ParseError::UnrecognizedDuration.into());
+            }
+            sum.add(&caps[1], unit)?;
+            part = &part[caps[0].len()..];
+        }
+    }
+    Ok(sum)
+}
+
+impl Parser {
+    /// Parses a duration such as `1w2d`, `3 weeks`, `1.5d` or `P1M2D`.
+    pub fn parse_duration(&self, input: &str) -> Result<CalendarDuration, anyhow::Error> {
+        let input = normalize(input);
+        parse(&self.rewrite(&input))
+    }
+}
+
+/// Parses a duration such as `1w2d`, `3 weeks`, `1.5d` or `P1M2D`.
+pub fn parse_duration(input: &str) -> Result<CalendarDuration, anyhow::Error> {
+    Parser::default().parse_duration(input)
+}
@@ -0,0 +1,135 @@
+use chrono::Datelike;
+use chrono::{Month, NaiveDate, Weekday};
+use std::fmt;
+
+/// Errors reported by the parser.
+///
+/// The public functions return `anyhow::Error`; the cause can be recovered
+/// with `err.downcast_ref::<ParseError>()`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseError {
+    /// The input does not match any supported format. `suggestions` holds
+    /// close inputs that do parse, best first.
+    Unrecognized { suggestions: Vec<String> },
+    /// The input has a supported format but names a day that does not exist.
+    InvalidDate,
+    /// The time of day given after a date does not exist.
+    InvalidTime,
+    /// The date, or a step in computing it, lies outside the supported range.
+    OutOfRange,
+    /// A duration gives the same unit twice (`1d2d`).
+    RepeatedUnit(&'static str),
+    /// The input to `parse_duration` is not a duration in units (`1w2d`) or
+    /// ISO-8601 (`P1M2D`).
+    UnrecognizedDuration,
+    /// The input is a sentinel such as `someday` that no date stands for;
+    /// `parse_resolved` accepts it.
+    Sentinel,
+    /// A leading weekday does not agree with the date that follows it.
+    WeekdayMismatch { date: NaiveDate, weekday: Weekday },
+    /// A CSV record has no field in the date column.
+    MissingField,
+    /// Any other failure, with its message.
+    Other(String),
+    /// The month has fewer than `nth` occurrences of the weekday.
+    NoSuchWeekday {
+        nth: u8,
+        weekday: Weekday,
+        year: i32,
+        month: u32,
+    },
+}
+
+impl ParseError {
+    pub(crate) fn unrecognized() -> Self {
+        ParseError::Unrecognized {
+            suggestions: Vec::new(),
+        }
+    }
+
+    /// The `ParseError` behind `err`, or `Other` with its message.
+    pub(crate) fn from_anyhow(err: anyhow::Error) -> Self {
+        err.downcast()
+            .unwrap_or_else(|err| ParseError::Other(format!("{:#}", err)))
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseError::Unrecognized { suggestions } => {
+                write!(f, "Unrecognized date format")?;
+                if let Some((last, rest)) = suggestions.split_last() {
+                    write!(f, ", did you mean ")?;
+                    for suggestion in rest {
+                        write!(f, "'{}', ", suggestion)?;
+                    }
+                    if !rest.is_empty() {
+                        write!(f, "or ")?;
+                    }
+                    write!(f, "'{}'?", last)?;
+                }
+                Ok(())
+            }
+            ParseError::InvalidDate => write!(f, "Invalid date"),
+            ParseError::InvalidTime => write!(f, "Invalid time"),
+            ParseError::OutOfRange => write!(f, "Date out of range"),
+            ParseError::RepeatedUnit(unit) => write!(f, "The unit '{}' is given twice", unit),
+            ParseError::UnrecognizedDuration => write!(f, "Unrecognized duration format"),
+            ParseError::Sentinel => write!(f, "Not a specific date"),
+            ParseError::MissingField => write!(f, "Missing date field"),
+            ParseError::Other(message) => write!(f, "{}", message),
+            ParseError::WeekdayMismatch { date, weekday } => write!(
+                f,
+                "{} is a {}, not a {}",
+                date,
+                weekday_name(date.weekday()),
+                weekday_name(*weekday)
+            ),
+            ParseError::NoSuchWeekday {
+                nth,
+                weekday,
+                year,
+                month,
+            } => write!(
+                f,
+                "{} {} has no {} {}",
+                month_name(*month),
+                year,
+                ordinal_name(*nth),
+                weekday_name(*weekday)
+            ),
+        }
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+fn weekday_name(weekday: Weekday) -> &'static str {
+    match weekday {
+        Weekday::Mon => "Monday",
+        Weekday::Tue => "Tuesday",
+        Weekday::Wed => "Wednesday",
+        Weekday::Thu => "Thursday",
+        Weekday::Fri => "Friday",
+        Weekday::Sat => "Saturday",
+        Weekday::Sun => "Sunday",
+    }
+}
+
+fn month_name(month: u32) -> &'static str {
+    u8::try_from(month)
+        .ok()
+        .and_then(|month| Month::try_from(month).ok())
+        .map_or("?", |month| month.name())
+}
+
+fn ordinal_name(nth: u8) -> &'static str {
+    match nth {
+        1 => "first",
+        2 => "second",
+        3 => "third",
+        4 => "fourth",
+        _ => "fifth",
+    }
+}
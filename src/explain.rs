@@ -0,0 +1,132 @@
+use chrono::{Local, NaiveDate};
+use std::fmt;
+
+use crate::{normalize, ParseError, Parser};
+
+/// How an expression was resolved, step by step.
+///
+/// ```
+/// use chrono::NaiveDate;
+/// use temporis::Parser;
+///
+/// let today = NaiveDate::from_ymd_opt(2026, 10, 18).unwrap();
+/// let explanation = Parser::new().explain_relative_to("monday-1d", today);
+/// assert_eq!(explanation.rule(), Some("ARITHMETIC"));
+/// assert_eq!(
+///     explanation.to_string(),
+///     "input: monday-1d\n\
+///      reference: 2026-10-18\n\
+///      KEYWORD: monday = 2026-10-19\n\
+///      ARITHMETIC: 2026-10-19 minus 1 day = 2026-10-18\n\
+///      result: 2026-10-18\n"
+/// );
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Explanation {
+    pub input: String,
+    /// Date relative expressions were resolved against.
+    pub reference: NaiveDate,
+    /// Rules applied, innermost first: the last one produced the result.
+    pub steps: Vec<Step>,
+    pub result: Result<NaiveDate, ParseError>,
+}
+
+/// Rule of the grammar applied to (part of) the input.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Step {
+    /// Name of the rule, such as `RELATIVE_TIME`.
+    pub rule: &'static str,
+    /// Part of the input the rule read.
+    pub input: String,
+    /// What the rule computed, such as `2024-01-31 plus 1m2d`.
+    pub detail: String,
+    pub date: NaiveDate,
+}
+
+impl Explanation {
+    /// Rule that produced the result, if any.
+    pub fn rule(&self) -> Option<&'static str> {
+        self.steps.last().map(|step| step.rule)
+    }
+}
+
+impl fmt::Display for Explanation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "input: {}", self.input)?;
+        writeln!(f, "reference: {}", self.reference)?;
+        for step in &self.steps {
+            writeln!(f, "{}: {} = {}", step.rule, step.detail, step.date)?;
+        }
+        match &self.result {
+            Ok(date) => writeln!(f, "result: {}", date),
+            Err(err) => writeln!(f, "error: {}", err),
+        }
+    }
+}
+
+/// Steps recorded while resolving; only kept when explaining, so that parsing
+/// does not pay for formatting them.
+#[derive(Debug, Default)]
+pub(crate) struct Trace {
+    steps: Option<Vec<Step>>,
+}
+
+impl Trace {
+    pub(crate) fn off() -> Self {
+        Trace { steps: None }
+    }
+
+    fn on() -> Self {
+        Trace {
+            steps: Some(Vec::new()),
+        }
+    }
+
+    /// Records that `rule` resolved `input` to `result`, when it succeeded.
+    pub(crate) fn record<D>(
+        &mut self,
+        rule: &'static str,
+        input: &str,
+        result: Result<NaiveDate, anyhow::Error>,
+        detail: impl FnOnce() -> D,
+    ) -> Result<NaiveDate, anyhow::Error>
+    where
+        D: fmt::Display,
+    {
+        if let (Some(steps), Ok(date)) = (&mut self.steps, &result) {
+            steps.push(Step {
+                rule,
+                input: input.to_string(),
+                detail: detail().to_string(),
+                date: *date,
+            });
+        }
+        result
+    }
+}
+
+impl Parser {
+    /// Explains how `input` resolves relative to today.
+    pub fn explain(&self, input: &str) -> Explanation {
+        self.explain_relative_to(input, Local::now().date_naive())
+    }
+
+    /// Explains how `input` resolves relative to the given reference date.
+    pub fn explain_relative_to(&self, input: &str, today: NaiveDate) -> Explanation {
+        let mut trace = Trace::on();
+        let result = self
+            .parse_traced(input, today, &mut trace)
+            .map_err(ParseError::from_anyhow);
+        Explanation {
+            input: normalize(input).into_owned(),
+            reference: today,
+            steps: trace.steps.unwrap_or_default(),
+            result,
+        }
+    }
+}
+
+/// Explains how `input` resolves relative to today.
+pub fn explain(input: &str) -> Explanation {
+    Parser::default().explain(input)
+}
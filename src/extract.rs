@@ -0,0 +1,140 @@
+use chrono::{Local, NaiveDate};
+use std::ops::Range;
+
+use crate::{normalize, Parser};
+
+/// Date expression found in a longer text.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Match {
+    /// Byte range of the expression in the text.
+    pub span: Range<usize>,
+    /// Expression as written in the text.
+    pub expr: String,
+    pub date: NaiveDate,
+}
+
+/// Longest expression tried, in words (`tuesday, january 16, 2024`, `first
+/// day of next month`).
+const MAX_WORDS: usize = 6;
+
+/// Keywords that are more often plain words; they only match as part of a
+/// longer expression (`may 5`, `sat 16 jan`).
+#[rustfmt::skip]
+const STOPWORDS: &[&str] = &[
+    "may", "now", "yes", "tom", "tod", "jan", "mar", "sat", "sun", "wed",
+];
+
+impl Parser {
+    /// Finds the date expressions in `text`, relative to today.
+    pub fn extract_dates(&self, text: &str) -> Vec<Match> {
+        self.extract_dates_relative_to(text, Local::now().date_naive())
+    }
+
+    /// Finds the date expressions in `text`, relative to the given reference
+    /// date. Where expressions overlap the one with the most words wins.
+    pub fn extract_dates_relative_to(&self, text: &str, today: NaiveDate) -> Vec<Match> {
+        let words: Vec<Range<usize>> = words(text).collect();
+
+        // Longest expression starting at each word
+        let mut candidates: Vec<(Range<usize>, Match)> = Vec::new();
+        for first in 0..words.len() {
+            for last in (first..words.len().min(first + MAX_WORDS)).rev() {
+                let span = trim(text, words[first].start..words[last].end);
+                let expr = &text[span.clone()];
+                if first == last && !stands_alone(expr) {
+                    continue;
+                }
+                let input = normalize(expr);
+                // A lone number word (`first`, `one`) is as vague as a digit
+                if first == last && self.read_numbers(&input) != input {
+                    continue;
+                }
+                if let Ok(date) = self.resolve(&self.rewrite(&input), today) {
+                    let found = Match {
+                        span,
+                        expr: expr.to_string(),
+                        date,
+                    };
+                    candidates.push((first..last + 1, found));
+                    break;
+                }
+            }
+        }
+
+        // Stable sort keeps the earliest of equally long expressions first
+        candidates.sort_by_key(|(words, _)| std::cmp::Reverse(words.len()));
+        let mut taken = vec![false; words.len()];
+        let mut matches = Vec::new();
+        for (words, found) in candidates {
+            if taken[words.clone()].iter().any(|taken| *taken) {
+                continue;
+            }
+            taken[words].iter_mut().for_each(|taken| *taken = true);
+            matches.push(found);
+        }
+        matches.sort_by_key(|found| found.span.start);
+        matches
+    }
+}
+
+/// Finds the date expressions in `text`, relative to today.
+///
+/// ```
+/// let text = "call Bob fri about the eom report";
+/// let matches = temporis::extract_dates(text);
+/// assert_eq!(matches[0].expr, "fri");
+/// assert_eq!(matches[1].expr, "eom");
+/// assert_eq!(temporis::strip_dates(text, &matches), "call Bob about the report");
+/// ```
+pub fn extract_dates(text: &str) -> Vec<Match> {
+    Parser::default().extract_dates(text)
+}
+
+/// Removes the matched expressions from `text`, collapsing the whitespace
+/// left behind.
+pub fn strip_dates(text: &str, matches: &[Match]) -> String {
+    let mut rest = String::with_capacity(text.len());
+    let mut copied = 0;
+    for found in matches {
+        rest.push_str(&text[copied..found.span.start]);
+        rest.push(' ');
+        copied = found.span.end;
+    }
+    rest.push_str(&text[copied..]);
+    rest.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// Byte ranges of the whitespace separated words of `text`.
+fn words(text: &str) -> impl Iterator<Item = Range<usize>> + '_ {
+    let mut rest = text.char_indices().peekable();
+    std::iter::from_fn(move || {
+        let (start, _) = rest.find(|(_, c)| !c.is_whitespace())?;
+        let mut end = text.len();
+        while let Some((index, c)) = rest.peek() {
+            if c.is_whitespace() {
+                end = *index;
+                break;
+            }
+            rest.next();
+        }
+        Some(start..end)
+    })
+}
+
+/// Narrows `span` to exclude the punctuation of the surrounding sentence.
+fn trim(text: &str, span: Range<usize>) -> Range<usize> {
+    let expr = &text[span.clone()];
+    let leading = expr.len() - expr.trim_start_matches(['(', '[', '"']).len();
+    let trailing = expr.len()
+        - expr
+            .trim_end_matches(['.', ',', ';', ':', '!', '?', ')', ']', '"'])
+            .len();
+    span.start + leading..(span.end - trailing).max(span.start + leading)
+}
+
+/// Whether a single word is distinctive enough to be a date on its own:
+/// bare numbers (`2024`, `1.5`) and common words (`may`) are not.
+fn stands_alone(word: &str) -> bool {
+    let word = word.to_lowercase();
+    !STOPWORDS.contains(&word.as_str()) && !word.chars().all(|c| c.is_ascii_digit() || c == '.')
+}
@@ -0,0 +1,184 @@
+use anyhow::anyhow;
+use chrono::{Local, NaiveDate};
+use std::collections::BTreeMap;
+use std::fmt;
+use std::sync::Arc;
+
+use crate::explain::Trace;
+use crate::{normalize, taskwarrior, ParseError, Parser, CONNECTIVES, KEYWORDS};
+
+/// Keywords defined by the caller, resolved alongside the built-in ones.
+///
+/// Names are single words of letters, digits and underscores. They take part
+/// in arithmetic (`release-2d`), nested expressions (`15th of fiscalclose`)
+/// and completion like any built-in keyword.
+///
+/// ```
+/// use chrono::{Datelike, NaiveDate};
+/// use temporis::{KeywordRegistry, Parser};
+///
+/// let mut keywords = KeywordRegistry::new();
+/// keywords.register_date("release", NaiveDate::from_ymd_opt(2024, 3, 1).unwrap()).unwrap();
+/// keywords.register_alias("payday", "last fri of month").unwrap();
+/// keywords
+///     .register_fn("sprintend", |today| today.checked_add_days(chrono::Days::new(14)))
+///     .unwrap();
+/// assert!(keywords.register_alias("eom", "today").is_err());
+///
+/// let parser = Parser::new().keywords(keywords);
+/// assert_eq!(parser.parse("release-2d").unwrap(), NaiveDate::from_ymd_opt(2024, 2, 28).unwrap());
+/// assert_eq!(parser.parse("payday").unwrap().weekday(), chrono::Weekday::Fri);
+/// ```
+#[derive(Clone, Default)]
+pub struct KeywordRegistry {
+    keywords: BTreeMap<String, Definition>,
+}
+
+/// What a custom keyword resolves to.
+#[derive(Clone)]
+enum Definition {
+    Date(NaiveDate),
+    /// Expression read like any input to `parse_date`, possibly using
+    /// keywords registered before it.
+    Alias(String),
+    /// Date computed from the reference date; `None` when it is out of range.
+    Function(Arc<dyn Fn(NaiveDate) -> Option<NaiveDate> + Send + Sync>),
+}
+
+impl KeywordRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Defines `name` as a fixed date.
+    pub fn register_date(&mut self, name: &str, date: NaiveDate) -> Result<(), anyhow::Error> {
+        self.register_date_with(&Parser::new(), name, date)
+    }
+
+    /// Defines `name` as a fixed date, checked against the words `parser`
+    /// already reads in its locale.
+    pub(crate) fn register_date_with(
+        &mut self,
+        parser: &Parser,
+        name: &str,
+        date: NaiveDate,
+    ) -> Result<(), anyhow::Error> {
+        self.register(parser, name, Definition::Date(date))
+    }
+
+    /// Defines `name` as another expression (`payday` as `last fri of month`).
+    /// The expression may only use keywords registered before it.
+    pub fn register_alias(&mut self, name: &str, expr: &str) -> Result<(), anyhow::Error> {
+        self.register_alias_with(&Parser::new(), name, expr)
+    }
+
+    /// Defines `name` as another expression, checked with the settings of
+    /// `parser` (its locale, Taskwarrior mode) rather than the defaults.
+    pub(crate) fn register_alias_with(
+        &mut self,
+        parser: &Parser,
+        name: &str,
+        expr: &str,
+    ) -> Result<(), anyhow::Error> {
+        let expr = normalize(expr).into_owned();
+        if let Err(err) = parser.clone().keywords(self.clone()).parse(&expr) {
+            if let Some(ParseError::Unrecognized { .. }) = err.downcast_ref::<ParseError>() {
+                return Err(anyhow!("Unrecognized expression '{}' for '{}'", expr, name));
+            }
+        }
+        self.register(parser, name, Definition::Alias(expr))
+    }
+
+    /// Defines `name` as a date computed from the reference date.
+    pub fn register_fn<F>(&mut self, name: &str, function: F) -> Result<(), anyhow::Error>
+    where
+        F: Fn(NaiveDate) -> Option<NaiveDate> + Send + Sync + 'static,
+    {
+        self.register(
+            &Parser::new(),
+            name,
+            Definition::Function(Arc::new(function)),
+        )
+    }
+
+    /// Registered names, in alphabetical order.
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.keywords.keys().map(String::as_str)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.keywords.is_empty()
+    }
+
+    fn register(
+        &mut self,
+        parser: &Parser,
+        name: &str,
+        definition: Definition,
+    ) -> Result<(), anyhow::Error> {
+        let name = name.to_lowercase();
+        let mut chars = name.chars();
+        let valid = chars.next().is_some_and(char::is_alphabetic)
+            && chars.all(|c| c.is_alphanumeric() || c == '_');
+        if !valid {
+            return Err(anyhow!("Invalid keyword name '{}'", name));
+        }
+        if self.keywords.contains_key(&name) {
+            return Err(anyhow!("Keyword '{}' is already registered", name));
+        }
+
+        // Built-in words and anything the grammar already reads in the
+        // locale of `parser` (nfri, 3d, three, French mars), including
+        // Taskwarrior's named dates so that its mode can be turned on
+        let builtin = KEYWORDS.contains(&name.as_str())
+            || CONNECTIVES.contains(&name.as_str())
+            || taskwarrior::NAMED_DATES.contains(&name.as_str())
+            || parser.rewrite(&name) != name
+            || !matches!(
+                parser
+                    .resolve(&name, Local::now().date_naive())
+                    .map_err(|err| err.downcast::<ParseError>()),
+                Err(Ok(ParseError::Unrecognized { .. }))
+            );
+        if builtin {
+            return Err(anyhow!("'{}' is a built-in keyword", name));
+        }
+
+        self.keywords.insert(name, definition);
+        Ok(())
+    }
+
+    /// Date of the keyword `input`, if it is one.
+    pub(crate) fn resolve(
+        &self,
+        parser: &Parser,
+        input: &str,
+        today: NaiveDate,
+        trace: &mut Trace,
+    ) -> Result<Option<NaiveDate>, anyhow::Error> {
+        let rule = "CUSTOM_KEYWORD";
+        let date = match self.keywords.get(input) {
+            None => return Ok(None),
+            Some(Definition::Date(date)) => {
+                trace.record(rule, input, Ok(*date), || format!("{} is fixed", input))
+            }
+            Some(Definition::Alias(expr)) => {
+                let date = parser.parse_traced(expr, today, trace);
+                trace.record(rule, input, date, || format!("{} is {}", input, expr))
+            }
+            Some(Definition::Function(function)) => {
+                let date = function(today).ok_or_else(|| ParseError::OutOfRange.into());
+                trace.record(rule, input, date, || {
+                    format!("{} computed from {}", input, today)
+                })
+            }
+        };
+        date.map(Some)
+    }
+}
+
+impl fmt::Debug for KeywordRegistry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_set().entries(self.names()).finish()
+    }
+}
@@ -1,358 +1,1355 @@
-use anyhow::{anyhow, Result};
+use anyhow::Result;
 use chrono::Datelike;
-use chrono::{DateTime, Duration, Local, NaiveDate, Weekday};
+use chrono::{Duration, Local, NaiveDate, NaiveDateTime, NaiveTime, Weekday};
 use lazy_static::lazy_static;
-use regex::Regex;
-use std::collections::HashMap;
+use regex::{Captures, Regex, RegexSet, SetMatches};
+use std::borrow::Cow;
+use std::str::FromStr;
+
+use duration::Unit;
+use explain::Trace;
+
+#[cfg(feature = "csv")]
+mod batch;
+#[cfg(feature = "clap")]
+pub mod clap;
+mod complete;
+#[cfg(feature = "config")]
+mod config;
+mod context;
+mod duration;
+mod error;
+mod explain;
+mod extract;
+mod keywords;
+mod locale;
+mod natural;
+mod numbers;
+mod predicate;
+mod range;
+#[cfg(feature = "serde")]
+pub mod serde;
+mod suggest;
+mod taskwarrior;
+
+#[cfg(feature = "csv")]
+pub use batch::{Column, Normalizer, Report, RowError};
+pub use complete::{complete, completion_script, Completion, Shell};
+#[cfg(feature = "config")]
+pub use config::ParserConfig;
+pub use context::ParseContext;
+pub use duration::{parse_duration, CalendarDuration};
+pub use error::ParseError;
+pub use explain::{explain, Explanation, Step};
+pub use extract::{extract_dates, strip_dates, Match};
+pub use keywords::KeywordRegistry;
+pub use locale::Locale;
+pub use natural::{NaturalDate, NaturalDateTime, NaturalRange};
+pub use predicate::{parse_predicate, DatePredicate};
+pub use range::{DateRange, Interval, Resolved};
 
 lazy_static! {
     static ref NEXT_WEEKDAY_REGEX: Regex = Regex::new(r"^n(monday|mon|tuesday|tue|wednesday|wed|thursday|thu|friday|fri|saturday|sat|sunday|sun)$").unwrap();
     static ref NUMBERED_WEEKDAY_REGEX: Regex = Regex::new(r"^(\d+)(monday|mon|tuesday|tue|wednesday|wed|thursday|thu|friday|fri|saturday|sat|sunday|sun)$").unwrap();
-    static ref DATE_REGEX_YMD: Regex = Regex::new(r"^(\d{4})[-/](\d{1,2})[-/](\d{1,2})$").unwrap();
-    static ref DATE_REGEX_DMY: Regex = Regex::new(r"^(\d{1,2})[-/](\d{1,2})[-/](\d{4})$").unwrap();
-    static ref DAY_MONTH_REGEX: Regex = Regex::new(r"^(\d{1,2})[-/]([a-zA-Z]+)$").unwrap();
-    static ref MONTH_DAY_REGEX: Regex = Regex::new(r"^([a-zA-Z]+)[-/](\d{1,2})$").unwrap();
-    static ref FULL_DATE_ALPHA_DMY: Regex = Regex::new(r"^(\d{1,2})[-/]([a-zA-Z]+)[-/](\d{4})$").unwrap();
-    static ref FULL_DATE_ALPHA_YMD: Regex = Regex::new(r"^(\d{4})[-/]([a-zA-Z]+)[-/](\d{1,2})$").unwrap();
+    static ref NTH_WEEKDAY_REGEX: Regex = Regex::new(r"^(first|second|third|fourth|fifth|last|1st|2nd|3rd|4th|5th|[1-5])\s*(monday|mon|tuesday|tue|wednesday|wed|thursday|thu|friday|fri|saturday|sat|sunday|sun)(?:(?:\s+(?:of|in)\s+(?:the\s+)?|\s*[-/]\s*)(.+))?$").unwrap();
+    // Written-out dates accept `-`, `/`, `.` or whitespace (optionally after a
+    // comma) between fields, an ordinal suffix on the day and `16th of jan`
+    static ref DAY_MONTH_REGEX: Regex = Regex::new(r"^(\d{1,2})(?:st|nd|rd|th)?(?:\s+of\s+|[-/]|\.\s*|,?\s+)([a-zA-Z]+)$").unwrap();
+    static ref MONTH_DAY_REGEX: Regex = Regex::new(r"^([a-zA-Z]+)(?:[-/]|\.\s*|,?\s+)(\d{1,2})(?:st|nd|rd|th)?$").unwrap();
+    static ref FULL_DATE_ALPHA_DMY: Regex = Regex::new(r"^(\d{1,2})(?:st|nd|rd|th)?(?:\s+of\s+|[-/]|\.\s*|,?\s+)([a-zA-Z]+)(?:[-/]|\.\s*|,?\s+)(\d{4}|'?\d{2})$").unwrap();
+    static ref FULL_DATE_ALPHA_MDY: Regex = Regex::new(r"^([a-zA-Z]+)(?:[-/]|\.\s*|,?\s+)(\d{1,2})(?:st|nd|rd|th)?(?:[-/]|\.\s*|,?\s+)(\d{4}|'?\d{2})$").unwrap();
+    static ref FULL_DATE_ALPHA_YMD: Regex = Regex::new(r"^(\d{4})(?:[-/]|\.\s*|,?\s+)([a-zA-Z]+)(?:[-/]|\.\s*|,?\s+)(\d{1,2})(?:st|nd|rd|th)?$").unwrap();
+    static ref LEADING_WEEKDAY_REGEX: Regex = Regex::new(r"^(monday|mon|tuesday|tue|wednesday|wed|thursday|thu|friday|fri|saturday|sat|sunday|sun)(?:,\s*|\s+)(.+)$").unwrap();
+    static ref YEAR_REGEX: Regex = Regex::new(r"^(\d{4}|'\d{2})$").unwrap();
+    static ref MONTH_YEAR_REGEX: Regex = Regex::new(r"^([a-zA-Z]+)(?:[-/]|\.\s*|,?\s+)(\d{4}|'\d{2})$").unwrap();
+    static ref YEAR_MONTH_REGEX: Regex = Regex::new(r"^(\d{4})(?:[-/]|\.\s*|,?\s+)(\d{1,2}|[a-zA-Z]+)$").unwrap();
+    static ref NUMERIC_MONTH_YEAR_REGEX: Regex = Regex::new(r"^(\d{1,2})[-/](\d{4})$").unwrap();
     static ref SHORT_DATE_REGEX: Regex = Regex::new(r"^(\d{1,2})[-/](\d{1,2})$").unwrap();
     static ref ORDINAL_DATE_REGEX: Regex = Regex::new(r"^(\d{1,2})(st|nd|rd|th)$").unwrap();
-    static ref RELATIVE_TIME_REGEX: Regex = Regex::new(
-        r"^(-?\d+)(d|day|days|w|wk|wks|week|weeks|m|mth|mths|month|months|y|yr|yrs|year|years)$"
-    ).unwrap();
-    static ref MONTH_MAP: HashMap<&'static str, u32> = {
-        let mut m = HashMap::new();
-        m.insert("jan", 1);
-        m.insert("january", 1);
-        m.insert("feb", 2);
-        m.insert("february", 2);
-        m.insert("mar", 3);
-        m.insert("march", 3);
-        m.insert("apr", 4);
-        m.insert("april", 4);
-        m.insert("may", 5);
-        m.insert("jun", 6);
-        m.insert("june", 6);
-        m.insert("jul", 7);
-        m.insert("july", 7);
-        m.insert("aug", 8);
-        m.insert("august", 8);
-        m.insert("sep", 9);
-        m.insert("september", 9);
-        m.insert("oct", 10);
-        m.insert("october", 10);
-        m.insert("nov", 11);
-        m.insert("november", 11);
-        m.insert("dec", 12);
-        m.insert("december", 12);
-        m
+    static ref NEGATIVE_ORDINAL_REGEX: Regex = Regex::new(r"^-(\d{1,2})(st|nd|rd|th)$").unwrap();
+    static ref DAY_OF_PERIOD_REGEX: Regex = Regex::new(r"^(-?\d{1,2}(?:st|nd|rd|th)(?:\s+day)?|first day|last day|penultimate day)(?:\s+of\s+(?:the\s+)?|\s+)(.+)$").unwrap();
+    // One or more amounts with a unit: `3d`, `1w3d`, `1 week and 3 days`;
+    // only a unit longer than a letter may follow a space
+    static ref UNIT_GROUPS: String = {
+        let names = || Unit::DATE.iter().flat_map(|unit| unit.names().iter().copied());
+        let letters: String = names().filter(|name| name.len() == 1).collect();
+        let words: Vec<_> = names().filter(|name| name.len() > 1).collect();
+        let group = format!(r"\d+(?:\s*(?:{})|[{}])", words.join("|"), letters);
+        format!(r"{group}(?:(?:\s*,\s*|\s+and\s+|\s*){group})*", group = group)
     };
+    static ref RELATIVE_TIME_REGEX: Regex =
+        Regex::new(&format!(r"^(-?)({})$", *UNIT_GROUPS)).unwrap();
+    static ref ARITHMETIC_REGEX: Regex =
+        Regex::new(&format!(r"^(.+?)\s*([+-])\s*({})$", *UNIT_GROUPS)).unwrap();
+    static ref TIME_REGEX: Regex = Regex::new(
+        r"^(?:at\s+|(.+?)\s+(?:at\s+)?|(.+?\d)t)?(\d{1,2})(?::(\d{2}))?(?::(\d{2}))?\s*(am|pm)?$"
+    ).unwrap();
+}
+
+/// Rules of the grammar backed by a regex, in the order they are tried.
+#[derive(Debug, Clone, Copy)]
+enum Rule {
+    NextWeekday,
+    NumberedWeekday,
+    NthWeekday,
+    Ordinal,
+    NegativeOrdinal,
+    RelativeTime,
+    DayOfPeriod,
+    LeadingWeekday,
+    DayMonth,
+    MonthDay,
+    FullDateDmy,
+    FullDateMdy,
+    FullDateYmd,
+    ShortDate,
+    Year,
+    MonthYear,
+    YearMonth,
+    NumericMonthYear,
+    Arithmetic,
+}
+
+impl Rule {
+    const ALL: [Rule; 19] = [
+        Rule::NextWeekday,
+        Rule::NumberedWeekday,
+        Rule::NthWeekday,
+        Rule::Ordinal,
+        Rule::NegativeOrdinal,
+        Rule::RelativeTime,
+        Rule::DayOfPeriod,
+        Rule::LeadingWeekday,
+        Rule::DayMonth,
+        Rule::MonthDay,
+        Rule::FullDateDmy,
+        Rule::FullDateMdy,
+        Rule::FullDateYmd,
+        Rule::ShortDate,
+        Rule::Year,
+        Rule::MonthYear,
+        Rule::YearMonth,
+        Rule::NumericMonthYear,
+        Rule::Arithmetic,
+    ];
+
+    fn regex(self) -> &'static Regex {
+        match self {
+            Rule::NextWeekday => &NEXT_WEEKDAY_REGEX,
+            Rule::NumberedWeekday => &NUMBERED_WEEKDAY_REGEX,
+            Rule::NthWeekday => &NTH_WEEKDAY_REGEX,
+            Rule::Ordinal => &ORDINAL_DATE_REGEX,
+            Rule::NegativeOrdinal => &NEGATIVE_ORDINAL_REGEX,
+            Rule::RelativeTime => &RELATIVE_TIME_REGEX,
+            Rule::DayOfPeriod => &DAY_OF_PERIOD_REGEX,
+            Rule::LeadingWeekday => &LEADING_WEEKDAY_REGEX,
+            Rule::DayMonth => &DAY_MONTH_REGEX,
+            Rule::MonthDay => &MONTH_DAY_REGEX,
+            Rule::FullDateDmy => &FULL_DATE_ALPHA_DMY,
+            Rule::FullDateMdy => &FULL_DATE_ALPHA_MDY,
+            Rule::FullDateYmd => &FULL_DATE_ALPHA_YMD,
+            Rule::ShortDate => &SHORT_DATE_REGEX,
+            Rule::Year => &YEAR_REGEX,
+            Rule::MonthYear => &MONTH_YEAR_REGEX,
+            Rule::YearMonth => &YEAR_MONTH_REGEX,
+            Rule::NumericMonthYear => &NUMERIC_MONTH_YEAR_REGEX,
+            Rule::Arithmetic => &ARITHMETIC_REGEX,
+        }
+    }
+}
+
+lazy_static! {
+    // Every rule's regex, at the index of `Rule as usize`
+    static ref RULE_SET: RegexSet =
+        RegexSet::new(Rule::ALL.iter().map(|rule| rule.regex().as_str())).unwrap();
+}
+
+/// Rules whose regex matches an input, found in a single pass over it; only
+/// the rule that handles the input then runs its own regex for the captures.
+#[derive(Debug, Clone)]
+struct Recognized(SetMatches);
+
+impl Recognized {
+    fn of(input: &str) -> Self {
+        Recognized(RULE_SET.matches(input))
+    }
+
+    fn captures<'a>(&self, rule: Rule, input: &'a str) -> Option<Captures<'a>> {
+        if self.0.matched(rule as usize) {
+            rule.regex().captures(input)
+        } else {
+            None
+        }
+    }
+}
+
+/// Recognition alone, for `benches/parse.rs`: the first rule matching an
+/// input, found in a single pass or by trying each regex in turn as the
+/// parser used to.
+#[doc(hidden)]
+pub mod recognition {
+    use super::{Recognized, Rule};
+
+    pub fn single_pass(input: &str) -> Option<usize> {
+        let recognized = Recognized::of(input);
+        Rule::ALL
+            .into_iter()
+            .find(|rule| recognized.captures(*rule, input).is_some())
+            .map(|rule| rule as usize)
+    }
+
+    pub fn cascade(input: &str) -> Option<usize> {
+        Rule::ALL
+            .into_iter()
+            .find(|rule| rule.regex().captures(input).is_some())
+            .map(|rule| rule as usize)
+    }
+}
+
+/// Words the grammar accepts on their own, in the order they are suggested.
+#[rustfmt::skip]
+const KEYWORDS: &[&str] = &[
+    "today", "tomorrow", "yesterday", "now", "tod", "tom", "yes",
+    "monday", "tuesday", "wednesday", "thursday", "friday", "saturday", "sunday",
+    "mon", "tue", "wed", "thu", "fri", "sat", "sun",
+    "nmonday", "ntuesday", "nwednesday", "nthursday", "nfriday", "nsaturday", "nsunday",
+    "nmon", "ntue", "nwed", "nthu", "nfri", "nsat", "nsun",
+    "sow", "soww", "som", "soq", "soy",
+    "eow", "eoww", "eom", "eoq", "eoy", "eonw", "eonm", "eonq", "eony",
+    "sopw", "sopm", "sopq", "sopy", "socw", "socm", "socq", "socy",
+    "sonw", "sonm", "sonq", "sony",
+    "eopw", "eopm", "eopq", "eopy", "eocw", "eocm", "eocq", "eocy",
+    "january", "february", "march", "april", "may", "june",
+    "july", "august", "september", "october", "november", "december",
+    "jan", "feb", "mar", "apr", "jun", "jul", "aug", "sep", "oct", "nov", "dec",
+];
+
+lazy_static! {
+    /// Words that only appear as part of a longer expression, units of date
+    /// offsets included.
+    static ref CONNECTIVES: Vec<&'static str> = [
+        "of", "the", "in", "this", "next", "last", "previous",
+        "first", "second", "third", "fourth", "fifth", "penultimate",
+        "st", "nd", "rd", "th",
+    ]
+    .into_iter()
+    .chain(Unit::DATE.iter().flat_map(|unit| unit.names().iter().copied()))
+    .collect();
+}
+
+/// Longest input worth matching; it also bounds the nesting of expressions
+/// such as `3rd of eoq`.
+const MAX_INPUT_LEN: usize = 256;
+
+/// Date parser with configurable options.
+///
+/// `parse_date` uses the default configuration; build a `Parser` to change it:
+///
+/// ```
+/// use temporis::Parser;
+///
+/// let parser = Parser::new().two_digit_year_window(20);
+/// let date = parser.parse("16/01/50").unwrap();
+/// ```
+#[derive(Debug, Clone)]
+pub struct Parser {
+    strict: bool,
+    autocorrect: bool,
+    two_digit_year_window: u32,
+    anchor: Anchor,
+    clamp_days: bool,
+    locale: Locale,
+    keywords: KeywordRegistry,
+    taskwarrior: bool,
+    couple: u32,
+    few: u32,
+}
+
+/// Day a whole period such as `march` or `2025` resolves to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Anchor {
+    /// First day of the period.
+    #[default]
+    Start,
+    /// Last day of the period.
+    End,
+}
+
+impl Default for Parser {
+    fn default() -> Self {
+        Parser {
+            strict: false,
+            autocorrect: false,
+            two_digit_year_window: 50,
+            anchor: Anchor::Start,
+            clamp_days: false,
+            locale: Locale::English,
+            keywords: KeywordRegistry::default(),
+            taskwarrior: false,
+            couple: 2,
+            few: 3,
+        }
+    }
 }
 
 pub fn parse_date(date_str: &str) -> Result<NaiveDate, anyhow::Error> {
-    let input = date_str.trim().to_lowercase();
-    let now = Local::now();
-
-    // Try standard date formats first
-    if let Some(caps) = DATE_REGEX_YMD.captures(&input) {
-        if let Ok(date) =
-            NaiveDate::from_ymd_opt(caps[1].parse()?, caps[2].parse()?, caps[3].parse()?)
-                .ok_or_else(|| anyhow!("Invalid date"))
-        {
+    Parser::default().parse(date_str)
+}
+
+/// Parses an expression into the span of days it covers.
+pub fn parse_range(date_str: &str) -> Result<DateRange, anyhow::Error> {
+    Parser::default().parse_range(date_str)
+}
+
+/// Parses an expression followed by an optional time of day.
+pub fn parse_datetime(date_str: &str) -> Result<NaiveDateTime, anyhow::Error> {
+    Parser::default().parse_datetime(date_str)
+}
+
+/// Parses an expression that may be a sentinel such as `someday`.
+pub fn parse_resolved(date_str: &str) -> Result<Resolved, anyhow::Error> {
+    Parser::default().parse_resolved(date_str)
+}
+
+/// Parses an expression or an interval with open ends such as `..eom`.
+pub fn parse_interval(date_str: &str) -> Result<Interval, anyhow::Error> {
+    Parser::default().parse_interval(date_str)
+}
+
+impl Parser {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Rejects ambiguous shorthand such as two-digit years.
+    pub fn strict(mut self, strict: bool) -> Self {
+        self.strict = strict;
+        self
+    }
+
+    /// Silently fixes a misspelled word when exactly one keyword is a single
+    /// edit away (`tomorow` is parsed as `tomorrow`).
+    pub fn autocorrect(mut self, autocorrect: bool) -> Self {
+        self.autocorrect = autocorrect;
+        self
+    }
+
+    /// Sets how many years after the reference year a two-digit year may
+    /// resolve to; anything further lands in the previous century (default 50).
+    pub fn two_digit_year_window(mut self, years: u32) -> Self {
+        self.two_digit_year_window = years.min(99);
+        self
+    }
+
+    /// Sets which day of a month or year given on its own is returned.
+    pub fn anchor(mut self, anchor: Anchor) -> Self {
+        self.anchor = anchor;
+        self
+    }
+
+    /// Moves days missing from a month to its last day (`31st` in April is
+    /// April 30th) instead of skipping to the next month that has them.
+    pub fn clamp_days(mut self, clamp: bool) -> Self {
+        self.clamp_days = clamp;
+        self
+    }
+
+    /// Sets the language of month names, weekdays and natural words.
+    pub fn locale(mut self, locale: Locale) -> Self {
+        self.locale = locale;
+        self
+    }
+
+    /// Adds custom keywords such as `payday` or `release` to the grammar.
+    pub fn keywords(mut self, keywords: KeywordRegistry) -> Self {
+        self.keywords = keywords;
+        self
+    }
+
+    /// Reads dates the way Taskwarrior does: its full set of named dates
+    /// (`sod`, `eopm`, `socw`, `easter`, `later`), ISO-8601 durations such as
+    /// `P1W` and its meanings of `sow`, `som`, `soq` and `soy` as the start of
+    /// the current period and of month names as the 1st of the next one.
+    pub fn taskwarrior(mut self, taskwarrior: bool) -> Self {
+        self.taskwarrior = taskwarrior;
+        self
+    }
+
+    /// Sets the amount `a couple of days` stands for (default 2).
+    pub fn couple(mut self, amount: u32) -> Self {
+        self.couple = amount;
+        self
+    }
+
+    /// Sets the amount `a few days` stands for (default 3).
+    pub fn few(mut self, amount: u32) -> Self {
+        self.few = amount;
+        self
+    }
+
+    /// Parses a date relative to today.
+    pub fn parse(&self, date_str: &str) -> Result<NaiveDate, anyhow::Error> {
+        self.parse_relative_to(date_str, Local::now().date_naive())
+    }
+
+    /// Parses a date relative to the given reference date.
+    pub fn parse_relative_to(
+        &self,
+        date_str: &str,
+        today: NaiveDate,
+    ) -> Result<NaiveDate, anyhow::Error> {
+        self.parse_traced(date_str, today, &mut Trace::off())
+    }
+
+    fn parse_traced(
+        &self,
+        date_str: &str,
+        today: NaiveDate,
+        trace: &mut Trace,
+    ) -> Result<NaiveDate, anyhow::Error> {
+        let input = normalize(date_str);
+        let input = self.rewrite(&input);
+        self.resolve_traced(&input, today, trace)
+            .or_else(|err| self.recover(&input, today, err, trace))
+    }
+
+    fn resolve(&self, input: &str, today: NaiveDate) -> Result<NaiveDate, anyhow::Error> {
+        self.resolve_traced(input, today, &mut Trace::off())
+    }
+
+    /// Resolves a normalized input, recording the rule that matched and the
+    /// rules of its nested expressions.
+    fn resolve_traced(
+        &self,
+        input: &str,
+        today: NaiveDate,
+        trace: &mut Trace,
+    ) -> Result<NaiveDate, anyhow::Error> {
+        if input.len() > MAX_INPUT_LEN {
+            return Err(ParseError::unrecognized().into());
+        }
+
+        if self.taskwarrior {
+            if let Some(date) = taskwarrior::keyword(input, today)? {
+                return trace.record("TASKWARRIOR", input, Ok(date), || input);
+            }
+        }
+        if let Some(date) = keyword(input, today)? {
+            return trace.record("KEYWORD", input, Ok(date), || input);
+        }
+        if sentinel(input).is_some() {
+            return Err(ParseError::Sentinel.into());
+        }
+        if let Some(date) = self.keywords.resolve(self, input, today, trace)? {
             return Ok(date);
         }
+
+        // Try standard date formats first
+        if let Some(date) = self.parse_numeric_date(input, today)? {
+            return trace.record("NUMERIC_DATE", input, Ok(date), || input);
+        }
+
+        let recognized = Recognized::of(input);
+
+        // Next week's weekday (nfriday)
+        if let Some(caps) = recognized.captures(Rule::NextWeekday, input) {
+            let weekday = parse_weekday(&caps[1])?;
+            let date = find_weekday_offset(today, weekday, 1);
+            return trace.record("NEXT_WEEKDAY", input, date, || {
+                format!("{} of next week", weekday)
+            });
+        }
+
+        // Numbered weekday (1friday, 2friday, etc.)
+        if let Some(caps) = recognized.captures(Rule::NumberedWeekday, input) {
+            let weeks_ahead = parse_number(&caps[1])?;
+            let weekday = parse_weekday(&caps[2])?;
+            let date = find_weekday_offset(today, weekday, weeks_ahead);
+            return trace.record("NUMBERED_WEEKDAY", input, date, || {
+                format!("{} of the week in {} weeks", weekday, weeks_ahead)
+            });
+        }
+
+        // Weekday within a month (2nd tue of march, last fri of month, 1mon-jun)
+        if let Some(caps) = recognized.captures(Rule::NthWeekday, input) {
+            let weekday = parse_weekday(&caps[2])?;
+            let nth = match &caps[1] {
+                "first" | "1st" | "1" => Some(1),
+                "second" | "2nd" | "2" => Some(2),
+                "third" | "3rd" | "3" => Some(3),
+                "fourth" | "4th" | "4" => Some(4),
+                "fifth" | "5th" | "5" => Some(5),
+                _ => None,
+            };
+            // An ordinal on its own (first monday) is the next such day of any
+            // month; `last fri` and `1 mon` are left unread
+            let ordinal_only = caps.get(3).is_none() && !caps[1].ends_with(char::is_numeric);
+            if let Some(nth) = nth.filter(|_| ordinal_only) {
+                let date = (0..=12)
+                    .map(|offset| add_months(today.year(), today.month(), offset))
+                    .filter_map(|(year, month)| {
+                        NaiveDate::from_weekday_of_month_opt(year, month, weekday, nth)
+                    })
+                    .find(|date| *date > today)
+                    .ok_or_else(|| ParseError::OutOfRange.into());
+                return trace.record("NTH_WEEKDAY", input, date, || {
+                    format!("next {} {} of a month", ordinal(nth as i32), weekday)
+                });
+            }
+            let period = match caps.get(3) {
+                Some(period) => self.parse_month_period(period.as_str(), today)?,
+                None => None,
+            };
+            if let Some((year, month)) = period {
+                let nth = match nth {
+                    Some(nth) => nth,
+                    None => {
+                        let date = last_weekday_of_month(year, month, weekday);
+                        return trace.record("NTH_WEEKDAY", input, date, || {
+                            format!("last {} of {}-{:02}", weekday, year, month)
+                        });
+                    }
+                };
+                let missing = ParseError::NoSuchWeekday {
+                    nth,
+                    weekday,
+                    year,
+                    month,
+                };
+                let date = NaiveDate::from_weekday_of_month_opt(year, month, weekday, nth)
+                    .ok_or_else(|| missing.into());
+                return trace.record("NTH_WEEKDAY", input, date, || {
+                    format!(
+                        "{} {} of {}-{:02}",
+                        ordinal(nth as i32),
+                        weekday,
+                        year,
+                        month
+                    )
+                });
+            }
+        }
+
+        // Ordinal dates (1st, 2nd, etc.)
+        if let Some(caps) = recognized.captures(Rule::Ordinal, input) {
+            let day: i32 = parse_number(&caps[1])?;
+            if (1..=31).contains(&day) {
+                let date = find_next_occurrence_of_day(today, day, self.clamp_days);
+                return trace.record("ORDINAL", input, date, || {
+                    format!("next {} of a month", ordinal(day))
+                });
+            }
+        }
+
+        // Ordinal dates counted from the end of the month (-1st, -2nd, etc.)
+        if let Some(caps) = recognized.captures(Rule::NegativeOrdinal, input) {
+            let day: i32 = parse_number(&caps[1])?;
+            if (1..=31).contains(&day) {
+                let date = find_next_occurrence_of_day(today, -day, self.clamp_days);
+                return trace.record("NEGATIVE_ORDINAL", input, date, || {
+                    format!("next {} to last day of a month", ordinal(day))
+                });
+            }
+        }
+
+        // Relative time expressions, years and months first and following
+        // the calendar (3d, 1m, 2y6m, 1 week 3 days)
+        if let Some(caps) = recognized.captures(Rule::RelativeTime, input) {
+            let groups = duration::groups(&caps[2])?;
+            let offset = duration::sum(&groups, &caps[1] == "-")?;
+            let date = offset
+                .add_to(today)
+                .ok_or_else(|| ParseError::OutOfRange.into());
+            return trace.record("RELATIVE_TIME", input, date, || match groups[..] {
+                [(amount, unit)] => {
+                    let amount = format!("{}{}", &caps[1], amount);
+                    format!("{} from {}", quantity(&amount, unit), today)
+                }
+                _ => format!("{} from {}", offset, today),
+            });
+        }
+
+        // Day of a given month (15th of next month, last day of feb, 3rd of eoq)
+        if let Some(caps) = recognized.captures(Rule::DayOfPeriod, input) {
+            let rest = &caps[2];
+            let day: i32 = match &caps[1] {
+                "first day" => 1,
+                "last day" => -1,
+                "penultimate day" => -2,
+                ordinal => {
+                    let ordinal = ordinal.trim_end_matches("day").trim_end();
+                    parse_number(&ordinal[..ordinal.len() - 2])?
+                }
+            };
+            if (1..=31).contains(&day.abs()) {
+                // `15th of jan` is the next occurrence of that day, like `15-jan`
+                if let Some(month) = month_number(rest).filter(|_| day > 0) {
+                    let date = find_next_occurrence(today, month, day as u32, self.clamp_days);
+                    return trace.record("DAY_OF_PERIOD", input, date, || {
+                        format!("next {} of {}", ordinal(day), rest)
+                    });
+                }
+                // A year has a first, last and penultimate day but no 15th
+                if let Some(range) = self
+                    .parse_period(rest, &Recognized::of(rest), today)?
+                    .filter(|range| range.start.month() != range.end.month())
+                {
+                    let date = match day {
+                        1 => Ok(range.start),
+                        -1 => Ok(range.end),
+                        -2 => add_days(range.end, -1),
+                        _ => Err(ParseError::unrecognized().into()),
+                    };
+                    return trace.record("DAY_OF_PERIOD", input, date, || {
+                        format!("{} day of {} to {}", ordinal(day), range.start, range.end)
+                    });
+                }
+                let (year, month) = match self.parse_month_period(rest, today)? {
+                    Some(period) => period,
+                    None => {
+                        let date = self.resolve_traced(rest, today, trace)?;
+                        (date.year(), date.month())
+                    }
+                };
+                let date = day_of_month(year, month, day, self.clamp_days)
+                    .ok_or_else(|| ParseError::InvalidDate.into());
+                return trace.record("DAY_OF_PERIOD", input, date, || {
+                    format!("{} day of {}-{:02}", ordinal(day), year, month)
+                });
+            }
+        }
+
+        // Written-out date led by its weekday (tue 16 jan, monday, january 15)
+        if let Some(caps) = recognized.captures(Rule::LeadingWeekday, input) {
+            let rest = &caps[2];
+            let recognized = Recognized::of(rest);
+            let date = match self.parse_numeric_date(rest, today)? {
+                Some(date) => Some(date),
+                None => self.parse_written_date(rest, &recognized, today)?,
+            };
+            if let Some(date) = date {
+                let weekday = parse_weekday(&caps[1])?;
+                if date.weekday() != weekday {
+                    return Err(ParseError::WeekdayMismatch { date, weekday }.into());
+                }
+                return trace.record("LEADING_WEEKDAY", input, Ok(date), || {
+                    format!("{} {}", weekday, rest)
+                });
+            }
+        }
+
+        if let Some(date) = self.parse_written_date(input, &recognized, today)? {
+            return trace.record("WRITTEN_DATE", input, Ok(date), || input);
+        }
+
+        // Whole months and years (march, 2025, mar-2025)
+        if let Some(range) = self.parse_period(input, &recognized, today)? {
+            let (end, date) = match self.anchor {
+                Anchor::Start => ("start", range.start),
+                Anchor::End => ("end", range.end),
+            };
+            return trace.record("PERIOD", input, Ok(date), || {
+                format!("{} of {}..{}", end, range.start, range.end)
+            });
+        }
+
+        // Offsets from another expression (eom-2d, friday + 1w)
+        if let Some(caps) = recognized.captures(Rule::Arithmetic, input) {
+            let base = self.resolve_traced(&caps[1], today, trace)?;
+            let groups = duration::groups(&caps[3])?;
+            let offset = duration::sum(&groups, false)?;
+            let shift = if &caps[2] == "-" { -offset } else { offset };
+            let date = shift
+                .add_to(base)
+                .ok_or_else(|| ParseError::OutOfRange.into());
+            let offset = match groups[..] {
+                [(amount, unit)] => quantity(amount, unit),
+                _ => offset.to_string(),
+            };
+            return trace.record("ARITHMETIC", input, date, || {
+                let sign = if &caps[2] == "+" { "plus" } else { "minus" };
+                format!("{} {} {}", base, sign, offset)
+            });
+        }
+
+        Err(ParseError::unrecognized().into())
     }
 
-    if let Some(caps) = DATE_REGEX_DMY.captures(&input) {
-        if let Ok(date) =
-            NaiveDate::from_ymd_opt(caps[3].parse()?, caps[2].parse()?, caps[1].parse()?)
-                .ok_or_else(|| anyhow!("Invalid date"))
+    /// Attaches suggestions to an unrecognized input, or parses its only
+    /// single-edit correction when autocorrect is enabled.
+    fn recover(
+        &self,
+        input: &str,
+        today: NaiveDate,
+        err: anyhow::Error,
+        trace: &mut Trace,
+    ) -> Result<NaiveDate, anyhow::Error> {
+        // Suggestions for oversized input would only cost time
+        if input.len() > MAX_INPUT_LEN
+            || !matches!(
+                err.downcast_ref::<ParseError>(),
+                Some(ParseError::Unrecognized { .. })
+            )
         {
-            return Ok(date);
+            return Err(err);
+        }
+
+        let mut keywords = Cow::Borrowed(KEYWORDS);
+        if !self.keywords.is_empty() {
+            keywords.to_mut().extend(self.keywords.names());
+        }
+        if self.taskwarrior {
+            keywords.to_mut().extend(taskwarrior::NAMED_DATES);
+        }
+        let corrections: Vec<_> = suggest::corrections(input, &keywords, &CONNECTIVES)
+            .into_iter()
+            .filter(|correction| self.resolve(&correction.text, today).is_ok())
+            .collect();
+        if self.autocorrect {
+            let mut typos = corrections.iter().filter(|c| c.max_distance == 1);
+            if let (Some(typo), None) = (typos.next(), typos.next()) {
+                let date = self.resolve_traced(&typo.text, today, trace);
+                return trace.record("CORRECTION", input, date, || {
+                    format!("'{}' read as '{}'", input, typo.text)
+                });
+            }
+        }
+        Err(ParseError::Unrecognized {
+            suggestions: corrections
+                .into_iter()
+                .take(5)
+                .map(|correction| correction.text)
+                .collect(),
+        }
+        .into())
+    }
+
+    /// Parses an expression into the span of days it covers, relative to today.
+    pub fn parse_range(&self, date_str: &str) -> Result<DateRange, anyhow::Error> {
+        self.parse_range_relative_to(date_str, Local::now().date_naive())
+    }
+
+    /// Parses an expression into the span of days it covers, relative to the
+    /// given reference date.
+    pub fn parse_range_relative_to(
+        &self,
+        date_str: &str,
+        today: NaiveDate,
+    ) -> Result<DateRange, anyhow::Error> {
+        let input = normalize(date_str);
+        let input = self.rewrite(&input);
+        if let Some(range) = self.parse_period(&input, &Recognized::of(&input), today)? {
+            return Ok(range);
+        }
+        self.resolve(&input, today)
+            .or_else(|err| self.recover(&input, today, err, &mut Trace::off()))
+            .map(DateRange::day)
+    }
+
+    /// Parses an expression that may be a sentinel (`later`, `someday`),
+    /// relative to today.
+    pub fn parse_resolved(&self, date_str: &str) -> Result<Resolved, anyhow::Error> {
+        self.parse_resolved_relative_to(date_str, Local::now().date_naive())
+    }
+
+    /// Parses an expression that may be a sentinel (`later`, `someday`),
+    /// relative to the given reference date. Sentinels are kept apart from
+    /// dates even in Taskwarrior mode, where `parse_date` gives `later` as
+    /// 9999-12-30.
+    pub fn parse_resolved_relative_to(
+        &self,
+        date_str: &str,
+        today: NaiveDate,
+    ) -> Result<Resolved, anyhow::Error> {
+        let input = normalize(date_str);
+        match sentinel(&input) {
+            Some(resolved) => Ok(resolved),
+            None => self.parse_relative_to(&input, today).map(Resolved::Date),
         }
     }
 
-    // Natural language dates
-    match input.as_str() {
-        "today" | "tod" | "now" => return Ok(now.date_naive()),
-        "yesterday" | "yes" => return Ok((now - Duration::days(1)).date_naive()),
-        "tomorrow" | "tom" => return Ok((now + Duration::days(1)).date_naive()),
-        _ => {}
-    }
-
-    // Weekdays
-    match input.as_str() {
-        "monday" | "mon" => return Ok(find_next_weekday(Weekday::Mon)),
-        "tuesday" | "tue" => return Ok(find_next_weekday(Weekday::Tue)),
-        "wednesday" | "wed" => return Ok(find_next_weekday(Weekday::Wed)),
-        "thursday" | "thu" => return Ok(find_next_weekday(Weekday::Thu)),
-        "friday" | "fri" => return Ok(find_next_weekday(Weekday::Fri)),
-        "saturday" | "sat" => return Ok(find_next_weekday(Weekday::Sat)),
-        "sunday" | "sun" => return Ok(find_next_weekday(Weekday::Sun)),
-        _ => {}
-    }
-
-    // Next week's weekday (nfriday)
-    if let Some(caps) = NEXT_WEEKDAY_REGEX.captures(&input) {
-        let weekday = match &caps[1] {
-            "monday" | "mon" => Weekday::Mon,
-            "tuesday" | "tue" => Weekday::Tue,
-            "wednesday" | "wed" => Weekday::Wed,
-            "thursday" | "thu" => Weekday::Thu,
-            "friday" | "fri" => Weekday::Fri,
-            "saturday" | "sat" => Weekday::Sat,
-            "sunday" | "sun" => Weekday::Sun,
-            _ => return Err(anyhow!("Invalid weekday")),
+    /// Parses an expression or an interval with open ends, relative to today.
+    pub fn parse_interval(&self, date_str: &str) -> Result<Interval, anyhow::Error> {
+        self.parse_interval_relative_to(date_str, Local::now().date_naive())
+    }
+
+    /// Parses an expression or an interval `start..end`, either end of which
+    /// may be left out (`..eom`, `2024-01-01..`), relative to the given
+    /// reference date. Periods give their first day as a start and their last
+    /// day as an end, so `jan..march` covers all of March.
+    pub fn parse_interval_relative_to(
+        &self,
+        date_str: &str,
+        today: NaiveDate,
+    ) -> Result<Interval, anyhow::Error> {
+        let input = normalize(date_str);
+        let (start, end) = match (input.split_once(".."), sentinel(&input)) {
+            (Some(ends), _) => ends,
+            (None, Some(resolved)) => return Ok(Interval::new(resolved, resolved)),
+            (None, None) => {
+                return self
+                    .parse_range_relative_to(&input, today)
+                    .map(Interval::from)
+            }
+        };
+
+        let bound = |expr: &str, open: Resolved| -> Result<Interval, anyhow::Error> {
+            let expr = expr.trim();
+            if expr.is_empty() {
+                return Ok(Interval::new(open, open));
+            }
+            self.parse_interval_relative_to(expr, today)
         };
-        return Ok(find_weekday_offset(weekday, 1));
-    }
-
-    // Numbered weekday (1friday, 2friday, etc.)
-    if let Some(caps) = NUMBERED_WEEKDAY_REGEX.captures(&input) {
-        let weeks_ahead: i64 = caps[1].parse()?;
-        let weekday = match &caps[2] {
-            "monday" | "mon" => Weekday::Mon,
-            "tuesday" | "tue" => Weekday::Tue,
-            "wednesday" | "wed" => Weekday::Wed,
-            "thursday" | "thu" => Weekday::Thu,
-            "friday" | "fri" => Weekday::Fri,
-            "saturday" | "sat" => Weekday::Sat,
-            "sunday" | "sun" => Weekday::Sun,
-            _ => return Err(anyhow!("Invalid weekday")),
+        if end.contains("..") {
+            return Err(ParseError::unrecognized().into());
+        }
+        Ok(Interval::new(
+            bound(start, Resolved::Always)?.start,
+            bound(end, Resolved::Never)?.end,
+        ))
+    }
+
+    /// Parses an expression followed by an optional time of day, relative to
+    /// now.
+    pub fn parse_datetime(&self, date_str: &str) -> Result<NaiveDateTime, anyhow::Error> {
+        self.parse_datetime_relative_to(date_str, Local::now().naive_local())
+    }
+
+    /// Parses an expression followed by an optional time of day (`eom 17:00`,
+    /// `tomorrow at 9am`, `2024-01-16T14:30:00`), relative to the given
+    /// reference instant. Without a time the day starts at midnight; `now`
+    /// is the reference instant itself.
+    pub fn parse_datetime_relative_to(
+        &self,
+        date_str: &str,
+        now: NaiveDateTime,
+    ) -> Result<NaiveDateTime, anyhow::Error> {
+        let input = normalize(date_str);
+        if input == "now" {
+            return Ok(now);
+        }
+        if self.taskwarrior {
+            if let Some(datetime) = taskwarrior::datetime(&input, now)? {
+                return Ok(datetime);
+            }
+        }
+
+        if let Some(caps) = TIME_REGEX.captures(&input) {
+            let meridiem = caps.get(6).map(|m| m.as_str());
+            // A lone number is part of the date, not an hour
+            if caps.get(4).is_some() || meridiem.is_some() {
+                let hour: u32 = parse_number(&caps[3])?;
+                let minute: u32 = caps.get(4).map_or(Ok(0), |m| parse_number(m.as_str()))?;
+                let second: u32 = caps.get(5).map_or(Ok(0), |m| parse_number(m.as_str()))?;
+                let hour = match meridiem {
+                    Some(_) if !(1..=12).contains(&hour) => {
+                        return Err(ParseError::InvalidTime.into())
+                    }
+                    Some("am") => hour % 12,
+                    Some(_) => hour % 12 + 12,
+                    None => hour,
+                };
+                let time =
+                    NaiveTime::from_hms_opt(hour, minute, second).ok_or(ParseError::InvalidTime)?;
+                let date = match caps.get(1).or_else(|| caps.get(2)) {
+                    Some(date) => self.parse_relative_to(date.as_str(), now.date())?,
+                    None => now.date(),
+                };
+                return Ok(date.and_time(time));
+            }
+        }
+
+        Ok(self
+            .parse_relative_to(&input, now.date())?
+            .and_time(NaiveTime::MIN))
+    }
+
+    fn parse_period(
+        &self,
+        input: &str,
+        recognized: &Recognized,
+        today: NaiveDate,
+    ) -> Result<Option<DateRange>, anyhow::Error> {
+        if let Some(month) = month_number(input) {
+            // The current year's month until it is over, then next year's
+            let year = if month < today.month() {
+                today.year() + 1
+            } else {
+                today.year()
+            };
+            return month_range(year, month).map(Some);
+        }
+
+        if let Some(caps) = recognized.captures(Rule::Year, input) {
+            let year = self.parse_year(&caps[1], today)?;
+            let start = NaiveDate::from_ymd_opt(year, 1, 1).ok_or(ParseError::InvalidDate)?;
+            let end = NaiveDate::from_ymd_opt(year, 12, 31).ok_or(ParseError::InvalidDate)?;
+            return Ok(Some(DateRange::new(start, end)));
+        }
+
+        let (year, month) = if let Some(caps) = recognized.captures(Rule::MonthYear, input) {
+            (self.parse_year(&caps[2], today)?, parse_month(&caps[1])?)
+        } else if let Some(caps) = recognized.captures(Rule::YearMonth, input) {
+            let month = match caps[2].parse() {
+                Ok(month) => month,
+                Err(_) => parse_month(&caps[2])?,
+            };
+            (parse_number(&caps[1])?, month)
+        } else if let Some(caps) = recognized.captures(Rule::NumericMonthYear, input) {
+            (parse_number(&caps[2])?, parse_number(&caps[1])?)
+        } else {
+            return Ok(None);
         };
-        return Ok(find_weekday_offset(weekday, weeks_ahead));
-    }
-
-    // Business period markers
-    match input.as_str() {
-        "sow" => return Ok(find_next_weekday(Weekday::Mon)),
-        "soww" => return Ok(find_next_weekday(Weekday::Mon)),
-        "som" => return Ok(start_of_next_month(now)),
-        "soq" => return Ok(start_of_next_quarter(now)),
-        "soy" => return Ok(start_of_next_year(now)),
-        "eow" => return Ok(find_next_weekday(Weekday::Mon) - Duration::days(1)),
-        "eoww" => return Ok(find_next_weekday(Weekday::Sat)),
-        "eom" => return Ok(end_of_current_month(now)),
-        "eoq" => return Ok(end_of_current_quarter(now)),
-        "eoy" => return Ok(end_of_current_year(now)),
-        "eonw" => return Ok(find_next_weekday(Weekday::Mon) + Duration::days(6)),
-        "eonm" => return Ok(end_of_next_month(now)),
-        "eonq" => return Ok(end_of_next_quarter(now)),
-        "eony" => return Ok(end_of_next_year(now)),
-        _ => {}
-    }
-
-    // Ordinal dates (1st, 2nd, etc.)
-    if let Some(caps) = ORDINAL_DATE_REGEX.captures(&input) {
-        let day: u32 = caps[1].parse()?;
-        if day <= 31 {
-            return find_next_occurrence_of_day(now, day);
-        }
-    }
-
-    // Relative time expressions
-    if let Some(caps) = RELATIVE_TIME_REGEX.captures(&input) {
-        let amount: i64 = caps[1].parse()?;
-        let unit = &caps[2];
-        let duration = match unit {
-            "d" | "day" | "days" => Duration::days(amount),
-            "w" | "wk" | "wks" | "week" | "weeks" => Duration::weeks(amount),
-            "m" | "mth" | "mths" | "month" | "months" => Duration::days(amount * 30),
-            "y" | "yr" | "yrs" | "year" | "years" => Duration::days(amount * 365),
-            _ => return Err(anyhow!("Invalid time unit")),
+        month_range(year, month).map(Some)
+    }
+
+    /// Resolves `month`, `next month`, `march` or `mar-2025` to a year and month.
+    fn parse_month_period(
+        &self,
+        input: &str,
+        today: NaiveDate,
+    ) -> Result<Option<(i32, u32)>, anyhow::Error> {
+        let offset = match input {
+            "month" | "this month" => 0,
+            "next month" => 1,
+            "last month" | "previous month" => -1,
+            _ => {
+                return Ok(self
+                    .parse_period(input, &Recognized::of(input), today)?
+                    .filter(|range| range.start.month() == range.end.month())
+                    .map(|range| (range.start.year(), range.start.month())));
+            }
         };
-        return Ok(now.date_naive() + duration);
+        Ok(Some(add_months(today.year(), today.month(), offset)))
     }
 
-    // Day-month formats
-    if let Some(caps) = DAY_MONTH_REGEX.captures(&input) {
-        let day: u32 = caps[1].parse()?;
-        let month = parse_month(&caps[2])?;
-        return Ok(find_next_occurrence(now.date_naive(), month, day)?);
+    fn parse_year(&self, year_str: &str, today: NaiveDate) -> Result<i32, anyhow::Error> {
+        let digits = year_str.trim_start_matches('\'');
+        if digits.len() == 4 {
+            return parse_number(digits);
+        }
+        if self.strict {
+            return Err(ParseError::unrecognized().into());
+        }
+
+        // Pick the year ending in these digits inside the window around today
+        let latest = today.year() + self.two_digit_year_window as i32;
+        Ok(latest - (latest - parse_number::<i32>(digits)?).rem_euclid(100))
     }
 
-    // Month-day formats
-    if let Some(caps) = MONTH_DAY_REGEX.captures(&input) {
-        let month = parse_month(&caps[1])?;
-        let day: u32 = caps[2].parse()?;
-        return Ok(find_next_occurrence(now.date_naive(), month, day)?);
+    fn parse_numeric_date(
+        &self,
+        input: &str,
+        today: NaiveDate,
+    ) -> Result<Option<NaiveDate>, anyhow::Error> {
+        let [first, second, third] = match numeric_fields(input) {
+            Some(fields) => fields,
+            None => return Ok(None),
+        };
+        let (year, month, day) = if first.len() == 4 && third.len() <= 2 {
+            (
+                parse_number(first)?,
+                parse_number(second)?,
+                parse_number(third)?,
+            )
+        } else if first.len() <= 2
+            && (third.len() == 4 || third.trim_start_matches('\'').len() == 2)
+        {
+            let year = self.parse_year(third, today)?;
+            (year, parse_number(second)?, parse_number(first)?)
+        } else {
+            return Ok(None);
+        };
+        NaiveDate::from_ymd_opt(year, month, day)
+            .map(Some)
+            .ok_or_else(|| ParseError::InvalidDate.into())
     }
 
-    // Full date with alpha month
-    if let Some(caps) = FULL_DATE_ALPHA_DMY.captures(&input) {
-        let day: u32 = caps[1].parse()?;
-        let month = parse_month(&caps[2])?;
-        let year: i32 = caps[3].parse()?;
-        return Ok(
-            NaiveDate::from_ymd_opt(year, month, day).ok_or_else(|| anyhow!("Invalid date"))?
-        );
+    fn parse_written_date(
+        &self,
+        input: &str,
+        recognized: &Recognized,
+        today: NaiveDate,
+    ) -> Result<Option<NaiveDate>, anyhow::Error> {
+        // Day-month formats
+        if let Some(caps) = recognized.captures(Rule::DayMonth, input) {
+            let day: u32 = parse_number(&caps[1])?;
+            let month = parse_month(&caps[2])?;
+            return find_next_occurrence(today, month, day, false).map(Some);
+        }
+
+        // Month-day formats
+        if let Some(caps) = recognized.captures(Rule::MonthDay, input) {
+            let month = parse_month(&caps[1])?;
+            let day: u32 = parse_number(&caps[2])?;
+            return find_next_occurrence(today, month, day, false).map(Some);
+        }
+
+        // Full date with alpha month
+        let (year, month, day) = if let Some(caps) = recognized.captures(Rule::FullDateDmy, input) {
+            let year = self.parse_year(&caps[3], today)?;
+            (year, parse_month(&caps[2])?, parse_number(&caps[1])?)
+        } else if let Some(caps) = recognized.captures(Rule::FullDateMdy, input) {
+            let year = self.parse_year(&caps[3], today)?;
+            (year, parse_month(&caps[1])?, parse_number(&caps[2])?)
+        } else if let Some(caps) = recognized.captures(Rule::FullDateYmd, input) {
+            (
+                parse_number(&caps[1])?,
+                parse_month(&caps[2])?,
+                parse_number(&caps[3])?,
+            )
+        } else if let Some(caps) = recognized.captures(Rule::ShortDate, input) {
+            // Short date (day/month with current year)
+            let day: u32 = parse_number(&caps[1])?;
+            let month: u32 = parse_number(&caps[2])?;
+            return find_next_occurrence(today, month, day, false).map(Some);
+        } else {
+            return Ok(None);
+        };
+        NaiveDate::from_ymd_opt(year, month, day)
+            .map(Some)
+            .ok_or_else(|| ParseError::InvalidDate.into())
     }
+}
+
+/// Resolves the words that are dates on their own (`today`, `fri`, `eom`).
+fn keyword(input: &str, today: NaiveDate) -> Result<Option<NaiveDate>, anyhow::Error> {
+    let date = match input {
+        // Natural language dates
+        "today" | "tod" | "now" => today,
+        "yesterday" | "yes" => add_days(today, -1)?,
+        "tomorrow" | "tom" => add_days(today, 1)?,
+
+        // Weekdays
+        "monday" | "mon" => find_next_weekday(today, Weekday::Mon)?,
+        "tuesday" | "tue" => find_next_weekday(today, Weekday::Tue)?,
+        "wednesday" | "wed" => find_next_weekday(today, Weekday::Wed)?,
+        "thursday" | "thu" => find_next_weekday(today, Weekday::Thu)?,
+        "friday" | "fri" => find_next_weekday(today, Weekday::Fri)?,
+        "saturday" | "sat" => find_next_weekday(today, Weekday::Sat)?,
+        "sunday" | "sun" => find_next_weekday(today, Weekday::Sun)?,
+
+        // Business period markers: the start of the next period and the end
+        // of the current one, unless a direction is given (`sopm`, `eocq`)
+        "sow" | "soww" => start_of_period(today, Period::Week, 1)?,
+        "som" => start_of_period(today, Period::Month, 1)?,
+        "soq" => start_of_period(today, Period::Quarter, 1)?,
+        "soy" => start_of_period(today, Period::Year, 1)?,
+        "eoww" => find_next_weekday(today, Weekday::Sat)?,
+        _ => match Marker::parse(input) {
+            Some(marker) if marker.period != Period::Day => marker.resolve(today)?,
+            _ => return Ok(None),
+        },
+    };
+    Ok(Some(date))
+}
 
-    if let Some(caps) = FULL_DATE_ALPHA_YMD.captures(&input) {
-        let year: i32 = caps[1].parse()?;
-        let month = parse_month(&caps[2])?;
-        let day: u32 = caps[3].parse()?;
-        return Ok(
-            NaiveDate::from_ymd_opt(year, month, day).ok_or_else(|| anyhow!("Invalid date"))?
-        );
+/// Resolves the words no date stands for.
+fn sentinel(input: &str) -> Option<Resolved> {
+    match input {
+        "later" | "someday" => Some(Resolved::Never),
+        _ => None,
     }
+}
 
-    // Short date (day/month with current year)
-    if let Some(caps) = SHORT_DATE_REGEX.captures(&input) {
-        let day: u32 = caps[1].parse()?;
-        let month: u32 = caps[2].parse()?;
-        return Ok(find_next_occurrence(now.date_naive(), month, day)?);
+/// Trims and lowercases `input`, borrowing it when there is nothing to change.
+fn normalize(input: &str) -> Cow<'_, str> {
+    let input = input.trim();
+    if !input.is_ascii() {
+        Cow::Owned(input.to_lowercase())
+    } else if input.bytes().any(|byte| byte.is_ascii_uppercase()) {
+        Cow::Owned(input.to_ascii_lowercase())
+    } else {
+        Cow::Borrowed(input)
     }
+}
 
-    Err(anyhow!("Unrecognized date format"))
+fn parse_weekday(weekday_str: &str) -> Result<Weekday, anyhow::Error> {
+    match weekday_str {
+        "monday" | "mon" => Ok(Weekday::Mon),
+        "tuesday" | "tue" => Ok(Weekday::Tue),
+        "wednesday" | "wed" => Ok(Weekday::Wed),
+        "thursday" | "thu" => Ok(Weekday::Thu),
+        "friday" | "fri" => Ok(Weekday::Fri),
+        "saturday" | "sat" => Ok(Weekday::Sat),
+        "sunday" | "sun" => Ok(Weekday::Sun),
+        _ => Err(ParseError::unrecognized().into()),
+    }
 }
 
 fn parse_month(month_str: &str) -> Result<u32, anyhow::Error> {
-    MONTH_MAP
-        .get(month_str.to_lowercase().as_str())
-        .copied()
-        .ok_or_else(|| anyhow!("Invalid month name"))
+    month_number(month_str).ok_or_else(|| ParseError::unrecognized().into())
+}
+
+/// Fields of an all-digit date such as `2024-01-16` or `16/01/'24`: three
+/// groups of digits split by `-` or `/`, the middle one of one or two digits
+/// and the last optionally after an apostrophe. Scanned by hand as this is the
+/// most common input.
+fn numeric_fields(input: &str) -> Option<[&str; 3]> {
+    let digits = |field: &str| !field.is_empty() && field.bytes().all(|b| b.is_ascii_digit());
+    let mut fields = input.split(['-', '/']);
+    let (first, second, third) = (fields.next()?, fields.next()?, fields.next()?);
+    let year = third.strip_prefix('\'').filter(|year| year.len() == 2);
+    let valid = fields.next().is_none()
+        && digits(first)
+        && digits(second)
+        && second.len() <= 2
+        && digits(year.unwrap_or(third));
+    valid.then_some([first, second, third])
+}
+
+/// `amount` of `unit` spelled out, such as `1 month` or `-2 weeks`.
+fn quantity(amount: &str, unit: &str) -> String {
+    let name = Unit::parse(unit).map_or("year", Unit::name);
+    let amount = amount.trim_start_matches('+');
+    let plural = if amount.trim_start_matches('-') == "1" {
+        ""
+    } else {
+        "s"
+    };
+    format!("{} {}{}", amount, name, plural)
+}
+
+/// `day` written as an ordinal, such as `1st` or `22nd`.
+fn ordinal(day: i32) -> String {
+    format!("{}{}", day, complete::ordinal_suffix(&day.to_string()))
+}
+
+/// Number of a lowercase month name or abbreviation.
+fn month_number(name: &str) -> Option<u32> {
+    match name {
+        "jan" | "january" => Some(1),
+        "feb" | "february" => Some(2),
+        "mar" | "march" => Some(3),
+        "apr" | "april" => Some(4),
+        "may" => Some(5),
+        "jun" | "june" => Some(6),
+        "jul" | "july" => Some(7),
+        "aug" | "august" => Some(8),
+        "sep" | "september" => Some(9),
+        "oct" | "october" => Some(10),
+        "nov" | "november" => Some(11),
+        "dec" | "december" => Some(12),
+        _ => None,
+    }
+}
+
+fn month_range(year: i32, month: u32) -> Result<DateRange, anyhow::Error> {
+    let start = NaiveDate::from_ymd_opt(year, month, 1).ok_or(ParseError::InvalidDate)?;
+    let next = if month == 12 {
+        NaiveDate::from_ymd_opt(year + 1, 1, 1)
+    } else {
+        NaiveDate::from_ymd_opt(year, month + 1, 1)
+    };
+    let end = add_days(next.ok_or(ParseError::OutOfRange)?, -1)?;
+    Ok(DateRange::new(start, end))
+}
+
+/// Shifts a date by a number of days, failing instead of overflowing.
+fn add_days(date: NaiveDate, days: i64) -> Result<NaiveDate, anyhow::Error> {
+    Duration::try_days(days)
+        .and_then(|duration| date.checked_add_signed(duration))
+        .ok_or_else(|| ParseError::OutOfRange.into())
+}
+
+/// Parses an unbounded run of digits, reporting overflow as out of range.
+fn parse_number<T: FromStr>(digits: &str) -> Result<T, anyhow::Error> {
+    digits.parse().map_err(|_| ParseError::OutOfRange.into())
+}
+
+fn add_months(year: i32, month: u32, offset: i32) -> (i32, u32) {
+    let index = year * 12 + month as i32 - 1 + offset;
+    (index.div_euclid(12), index.rem_euclid(12) as u32 + 1)
 }
 
-fn find_next_weekday(weekday: Weekday) -> NaiveDate {
-    let now = Local::now();
-    let today_weekday = now.weekday();
+fn last_weekday_of_month(
+    year: i32,
+    month: u32,
+    weekday: Weekday,
+) -> Result<NaiveDate, anyhow::Error> {
+    let end = month_range(year, month)?.end;
+    let days_back = (end.weekday().num_days_from_monday() + 7 - weekday.num_days_from_monday()) % 7;
+    add_days(end, -(days_back as i64))
+}
+
+fn find_next_weekday(today: NaiveDate, weekday: Weekday) -> Result<NaiveDate, anyhow::Error> {
+    let today_weekday = today.weekday();
     let mut days_until_target =
         weekday.num_days_from_monday() as i64 - today_weekday.num_days_from_monday() as i64;
     if days_until_target <= 0 {
         days_until_target += 7;
     }
-    now.date_naive() + Duration::days(days_until_target)
+    add_days(today, days_until_target)
 }
 
-fn find_next_occurrence_of_day(now: DateTime<Local>, day: u32) -> Result<NaiveDate, anyhow::Error> {
-    let mut month = now.month();
-    let mut year = now.year();
-    let start_year = year;
-
-    if now.day() >= day {
-        month += 1;
-        if month > 12 {
-            month = 1;
-            year += 1;
-        }
+/// Day `day` of a month, counted back from its end when negative.
+fn day_of_month(year: i32, month: u32, day: i32, clamp: bool) -> Option<NaiveDate> {
+    let last = month_range(year, month).ok()?.end.day() as i32;
+    let mut day = if day < 0 { last + 1 + day } else { day };
+    if clamp {
+        day = day.clamp(1, last);
     }
+    if day < 1 || day > last {
+        return None;
+    }
+    NaiveDate::from_ymd_opt(year, month, day as u32)
+}
 
-    loop {
-        if let Some(date) = NaiveDate::from_ymd_opt(year, month, day) {
-            return Ok(date);
-        }
-        month += 1;
-        if month > 12 {
-            month = 1;
-            year += 1;
-        }
-        if year > start_year + 2 {
-            return Err(anyhow!(
-                "Could not find valid date within reasonable timeframe"
-            ));
+fn find_next_occurrence_of_day(
+    today: NaiveDate,
+    day: i32,
+    clamp: bool,
+) -> Result<NaiveDate, anyhow::Error> {
+    for offset in 0..=24 {
+        let (year, month) = add_months(today.year(), today.month(), offset);
+        if let Some(date) = day_of_month(year, month, day, clamp) {
+            if date > today {
+                return Ok(date);
+            }
         }
     }
+    Err(ParseError::OutOfRange.into())
 }
 
 fn find_next_occurrence(
     today: NaiveDate,
     month: u32,
     day: u32,
+    clamp: bool,
 ) -> Result<NaiveDate, anyhow::Error> {
     let this_year = today.year();
     let next_year = this_year + 1;
 
-    if let Some(date) = NaiveDate::from_ymd_opt(this_year, month, day) {
+    if let Some(date) = day_of_month(this_year, month, day as i32, clamp) {
         if date >= today {
             return Ok(date);
         }
     }
 
-    NaiveDate::from_ymd_opt(next_year, month, day)
-        .ok_or_else(|| anyhow!("Invalid date: the specified day does not exist for this month"))
+    day_of_month(next_year, month, day as i32, clamp).ok_or_else(|| ParseError::InvalidDate.into())
 }
 
-fn start_of_next_month(now: DateTime<Local>) -> NaiveDate {
-    let mut year = now.year();
-    let mut month = now.month();
-    month += 1;
-    if month > 12 {
-        year += 1;
-        month = 1;
-    }
-    NaiveDate::from_ymd_opt(year, month, 1).expect("Invalid date in start_of_next_month")
+/// Calendar period a boundary marker such as `sopm` refers to. Weeks start on
+/// Monday; the work week runs from Monday to Friday.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Period {
+    Day,
+    Week,
+    WorkWeek,
+    Month,
+    Quarter,
+    Year,
 }
 
-fn start_of_next_quarter(now: DateTime<Local>) -> NaiveDate {
-    let mut month = ((now.month() - 1) / 3 + 1) * 3 + 1;
-    let mut year = now.year();
-    if month > 12 {
-        month -= 12;
-        year += 1;
-    }
-    NaiveDate::from_ymd_opt(year, month, 1).expect("Invalid date in start_of_next_quarter")
+/// Start or end of a period relative to the current one: `so` or `eo`, then
+/// the direction (nothing or `c` for the current period, `p` for the previous
+/// one, `n` for the next one, `2n` or `3p` for further ones), then `d`, `w`,
+/// `m`, `q` or `y`. `soww` and `eoww` bound the work week.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Marker {
+    end: bool,
+    offset: i32,
+    period: Period,
 }
 
-fn start_of_next_year(now: DateTime<Local>) -> NaiveDate {
-    NaiveDate::from_ymd_opt(now.year() + 1, 1, 1).expect("Invalid date in start_of_next_year")
-}
+impl Marker {
+    fn parse(input: &str) -> Option<Marker> {
+        let (end, rest) = match input.strip_prefix("so") {
+            Some(rest) => (false, rest),
+            None => (true, input.strip_prefix("eo")?),
+        };
+        if rest == "ww" {
+            return Some(Marker {
+                end,
+                offset: 0,
+                period: Period::WorkWeek,
+            });
+        }
 
-fn end_of_current_month(now: DateTime<Local>) -> NaiveDate {
-    let mut year = now.year();
-    let mut month = now.month();
-    month += 1;
-    if month > 12 {
-        year += 1;
-        month = 1;
+        let (at, _) = rest.char_indices().next_back()?;
+        let (direction, unit) = rest.split_at(at);
+        let period = match unit {
+            "d" => Period::Day,
+            "w" => Period::Week,
+            "m" => Period::Month,
+            "q" => Period::Quarter,
+            "y" => Period::Year,
+            _ => return None,
+        };
+        let offset = match direction {
+            "" | "c" => 0,
+            "p" => -1,
+            "n" => 1,
+            _ => {
+                let (count, sign) = match direction.strip_suffix('n') {
+                    Some(count) => (count, 1),
+                    None => (direction.strip_suffix('p')?, -1),
+                };
+                if !count.bytes().all(|b| b.is_ascii_digit()) {
+                    return None;
+                }
+                sign * count.parse::<i32>().ok().filter(|count| *count > 0)?
+            }
+        };
+        Some(Marker {
+            end,
+            offset,
+            period,
+        })
     }
-    NaiveDate::from_ymd_opt(year, month, 1).expect("Invalid date in end_of_current_month")
-        - Duration::days(1)
-}
 
-fn end_of_current_quarter(now: DateTime<Local>) -> NaiveDate {
-    let mut month = ((now.month() - 1) / 3 + 1) * 3 + 1;
-    let mut year = now.year();
-    if month > 12 {
-        month -= 12;
-        year += 1;
+    fn resolve(self, today: NaiveDate) -> Result<NaiveDate, anyhow::Error> {
+        if self.end {
+            end_of_period(today, self.period, self.offset)
+        } else {
+            start_of_period(today, self.period, self.offset)
+        }
     }
-    NaiveDate::from_ymd_opt(year, month, 1).expect("Invalid date in end_of_current_quarter")
-        - Duration::days(1)
 }
 
-fn end_of_current_year(now: DateTime<Local>) -> NaiveDate {
-    NaiveDate::from_ymd_opt(now.year() + 1, 1, 1).expect("Invalid date in end_of_current_year")
-        - Duration::days(1)
+/// First day of the period `offset` periods away from the one holding `today`.
+fn start_of_period(
+    today: NaiveDate,
+    period: Period,
+    offset: i32,
+) -> Result<NaiveDate, anyhow::Error> {
+    let offset = i64::from(offset);
+    let months = match period {
+        Period::Day => return add_days(today, offset),
+        Period::Week | Period::WorkWeek => {
+            let monday = i64::from(today.weekday().num_days_from_monday());
+            return add_days(today, offset * 7 - monday);
+        }
+        Period::Month => offset,
+        Period::Quarter => offset * 3 - i64::from((today.month() - 1) % 3),
+        Period::Year => offset * 12 - i64::from(today.month() - 1),
+    };
+    let index = i64::from(today.year()) * 12 + i64::from(today.month() - 1) + months;
+    i32::try_from(index.div_euclid(12))
+        .ok()
+        .and_then(|year| NaiveDate::from_ymd_opt(year, index.rem_euclid(12) as u32 + 1, 1))
+        .ok_or_else(|| ParseError::OutOfRange.into())
 }
 
-fn end_of_next_month(now: DateTime<Local>) -> NaiveDate {
-    let mut year = now.year();
-    let mut month = now.month() + 2; // Add 2 to get to end of next month
-    if month > 12 {
-        year += 1;
-        month -= 12;
+/// Last day of the period `offset` periods away from the one holding `today`.
+fn end_of_period(
+    today: NaiveDate,
+    period: Period,
+    offset: i32,
+) -> Result<NaiveDate, anyhow::Error> {
+    if period == Period::WorkWeek {
+        return add_days(start_of_period(today, period, offset)?, 4);
     }
-    NaiveDate::from_ymd_opt(year, month, 1).expect("Invalid date in end_of_next_month")
-        - Duration::days(1)
-}
-
-fn end_of_next_quarter(now: DateTime<Local>) -> NaiveDate {
-    let current_quarter = (now.month() - 1) / 3;
-    let next_quarter = current_quarter + 2; // Add 2 to get to end of next quarter
-    let year = now.year() + (next_quarter as i32 / 4);
-    let month = ((next_quarter % 4) * 3) + 1;
-    NaiveDate::from_ymd_opt(year, month, 1).expect("Invalid date in end_of_next_quarter")
-        - Duration::days(1)
+    let next = offset.checked_add(1).ok_or(ParseError::OutOfRange)?;
+    add_days(start_of_period(today, period, next)?, -1)
 }
 
-fn end_of_next_year(now: DateTime<Local>) -> NaiveDate {
-    NaiveDate::from_ymd_opt(now.year() + 2, 1, 1).expect("Invalid date in end_of_next_year")
-        - Duration::days(1)
-}
-
-fn find_weekday_offset(weekday: Weekday, weeks_ahead: i64) -> NaiveDate {
-    let now = Local::now();
-    let today_weekday = now.weekday();
+fn find_weekday_offset(
+    today: NaiveDate,
+    weekday: Weekday,
+    weeks_ahead: i64,
+) -> Result<NaiveDate, anyhow::Error> {
+    let today_weekday = today.weekday();
     let mut days_until_target =
         weekday.num_days_from_monday() as i64 - today_weekday.num_days_from_monday() as i64;
 
@@ -360,6 +1357,9 @@ fn find_weekday_offset(weekday: Weekday, weeks_ahead: i64) -> NaiveDate {
         days_until_target += 7;
     }
 
-    days_until_target += weeks_ahead * 7;
-    now.date_naive() + Duration::days(days_until_target)
+    let days_ahead = weeks_ahead
+        .checked_mul(7)
+        .and_then(|days| days.checked_add(days_until_target))
+        .ok_or(ParseError::OutOfRange)?;
+    add_days(today, days_ahead)
 }
@@ -0,0 +1,146 @@
+use anyhow::anyhow;
+use std::borrow::Cow;
+use std::str::FromStr;
+
+use crate::duration::Unit;
+
+/// Language the words of an expression are written in.
+///
+/// Other locales translate month names, weekdays, units of time and `today`,
+/// `tomorrow` and `yesterday` into English before parsing, so `16 janvier 2025`
+/// and `16. Januar 2025` both parse; the rest of the grammar stays English.
+/// Number words are read in the language of the locale (`trois jours`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Locale {
+    #[default]
+    English,
+    French,
+    German,
+    Spanish,
+}
+
+/// Localized words and their English counterparts; an empty counterpart drops
+/// the word (`16 de enero` is `16 enero`).
+#[rustfmt::skip]
+const FRENCH: &[(&str, &str)] = &[
+    ("aujourd'hui", "today"), ("demain", "tomorrow"), ("hier", "yesterday"),
+    ("lundi", "monday"), ("mardi", "tuesday"), ("mercredi", "wednesday"), ("jeudi", "thursday"),
+    ("vendredi", "friday"), ("samedi", "saturday"), ("dimanche", "sunday"),
+    ("lun", "mon"), ("mer", "wed"), ("jeu", "thu"), ("ven", "fri"), ("sam", "sat"), ("dim", "sun"),
+    ("janvier", "january"), ("février", "february"), ("fevrier", "february"), ("mars", "march"),
+    ("avril", "april"), ("mai", "may"), ("juin", "june"), ("juillet", "july"), ("août", "august"),
+    ("aout", "august"), ("septembre", "september"), ("octobre", "october"),
+    ("novembre", "november"), ("décembre", "december"), ("decembre", "december"),
+    ("janv", "jan"), ("févr", "feb"), ("fevr", "feb"), ("avr", "apr"), ("juil", "jul"),
+    ("sept", "sep"), ("déc", "dec"),
+    ("jour", "day"), ("jours", "days"), ("semaine", "week"), ("semaines", "weeks"),
+    ("mois", "months"), ("an", "year"), ("ans", "years"), ("année", "year"), ("annee", "year"),
+    ("années", "years"), ("annees", "years"), ("et", "and"),
+    ("le", ""),
+];
+
+#[rustfmt::skip]
+const GERMAN: &[(&str, &str)] = &[
+    ("heute", "today"), ("morgen", "tomorrow"), ("gestern", "yesterday"),
+    ("montag", "monday"), ("dienstag", "tuesday"), ("mittwoch", "wednesday"),
+    ("donnerstag", "thursday"), ("freitag", "friday"), ("samstag", "saturday"),
+    ("sonntag", "sunday"),
+    ("mo", "mon"), ("di", "tue"), ("mi", "wed"), ("do", "thu"), ("fr", "fri"), ("sa", "sat"), ("so", "sun"),
+    ("januar", "january"), ("februar", "february"), ("märz", "march"), ("maerz", "march"),
+    ("mai", "may"), ("juni", "june"), ("juli", "july"), ("oktober", "october"), ("dezember", "december"),
+    ("mär", "mar"), ("okt", "oct"), ("dez", "dec"),
+    ("tag", "day"), ("tage", "days"), ("tagen", "days"), ("woche", "week"), ("wochen", "weeks"),
+    ("monat", "month"), ("monate", "months"), ("monaten", "months"), ("jahr", "year"),
+    ("jahre", "years"), ("jahren", "years"), ("und", "and"),
+    ("am", ""), ("den", ""),
+];
+
+#[rustfmt::skip]
+const SPANISH: &[(&str, &str)] = &[
+    ("hoy", "today"), ("mañana", "tomorrow"), ("ayer", "yesterday"),
+    ("lunes", "monday"), ("martes", "tuesday"), ("miércoles", "wednesday"),
+    ("miercoles", "wednesday"), ("jueves", "thursday"), ("viernes", "friday"),
+    ("sábado", "saturday"), ("sabado", "saturday"), ("domingo", "sunday"),
+    ("lun", "mon"), ("mié", "wed"), ("mie", "wed"), ("jue", "thu"), ("vie", "fri"),
+    ("sáb", "sat"), ("dom", "sun"),
+    ("enero", "january"), ("febrero", "february"), ("marzo", "march"), ("abril", "april"),
+    ("mayo", "may"), ("junio", "june"), ("julio", "july"), ("agosto", "august"),
+    ("septiembre", "september"), ("setiembre", "september"), ("octubre", "october"),
+    ("noviembre", "november"), ("diciembre", "december"),
+    ("ene", "jan"), ("abr", "apr"), ("ago", "aug"), ("dic", "dec"),
+    ("día", "day"), ("dia", "day"), ("días", "days"), ("dias", "days"), ("semana", "week"),
+    ("semanas", "weeks"), ("mes", "month"), ("meses", "months"), ("año", "year"),
+    ("años", "years"), ("y", "and"),
+    ("de", ""), ("del", ""), ("el", ""),
+];
+
+impl Locale {
+    fn words(self) -> &'static [(&'static str, &'static str)] {
+        match self {
+            Locale::English => &[],
+            Locale::French => FRENCH,
+            Locale::German => GERMAN,
+            Locale::Spanish => SPANISH,
+        }
+    }
+
+    /// Whether the locale translates `word`.
+    pub(crate) fn translates(self, word: &str) -> bool {
+        self.translation(word).is_some()
+    }
+
+    /// English counterpart of a localized `word`.
+    pub(crate) fn translation(self, word: &str) -> Option<&'static str> {
+        self.words()
+            .iter()
+            .find(|(local, _)| *local == word)
+            .map(|(_, english)| *english)
+    }
+
+    /// Rewrites the localized words of a lowercase `input` into English.
+    pub(crate) fn translate(self, input: &str) -> Cow<'_, str> {
+        let words = self.words();
+        if words.is_empty() {
+            return Cow::Borrowed(input);
+        }
+
+        let is_word = |c: char| c.is_alphabetic() || c == '\'';
+        let mut output = String::with_capacity(input.len());
+        let mut rest = input;
+        while let Some(start) = rest.find(is_word) {
+            let end = rest[start..]
+                .find(|c| !is_word(c))
+                .map_or(rest.len(), |n| start + n);
+            let word = &rest[start..end];
+            output.push_str(&rest[..start]);
+            // An English unit stuck to digits stays one (`1y`, though `y` is
+            // Spanish for and); a local unit is translated (`3jours`)
+            let attached = rest[..start].ends_with(|c: char| c.is_ascii_digit())
+                || rest[end..].starts_with(|c: char| c.is_ascii_digit());
+            let unit = attached && Unit::parse(word).is_some();
+            match words.iter().find(|(local, _)| *local == word && !unit) {
+                Some((_, english)) => output.push_str(english),
+                None => output.push_str(word),
+            }
+            rest = &rest[end..];
+        }
+        output.push_str(rest);
+        Cow::Owned(output.trim().to_string())
+    }
+}
+
+impl FromStr for Locale {
+    type Err = anyhow::Error;
+
+    /// Reads a language name or tag such as `fr`, `de_DE.UTF-8` or `spanish`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let language = s.split(['_', '-', '.', '@']).next().unwrap_or_default();
+        match language.to_lowercase().as_str() {
+            "en" | "english" | "c" | "posix" => Ok(Locale::English),
+            "fr" | "french" => Ok(Locale::French),
+            "de" | "german" => Ok(Locale::German),
+            "es" | "spanish" => Ok(Locale::Spanish),
+            _ => Err(anyhow!("Unsupported locale '{}'", s)),
+        }
+    }
+}
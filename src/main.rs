@@ -0,0 +1,257 @@
+use anyhow::{anyhow, Context, Result};
+use chrono::format::{Item, StrftimeItems};
+use chrono::{FixedOffset, Local, NaiveDate, Utc};
+use clap::Parser as _;
+use std::env;
+use std::fs::File;
+use std::io::{self, BufRead, BufReader, BufWriter, Read, Write};
+use std::path::PathBuf;
+use std::process::ExitCode;
+use temporis::{Column, Locale, Normalizer, ParseContext, ParseError, ParserConfig};
+
+/// Exit code for input that is not a date expression.
+const EXIT_UNRECOGNIZED: u8 = 3;
+
+/// Exit code for expressions naming a date that does not exist.
+const EXIT_INVALID: u8 = 4;
+
+/// Parse natural date expressions such as `tomorrow`, `eom` or `2nd tue of march`.
+#[derive(clap::Parser)]
+#[command(
+    name = "temporis",
+    version,
+    about,
+    args_conflicts_with_subcommands = true
+)]
+struct Args {
+    #[command(subcommand)]
+    command: Option<Command>,
+
+    /// Expressions to parse, each printed on its own line; one expression per
+    /// line is read from stdin when omitted
+    expression: Vec<String>,
+
+    /// strftime format of the printed dates
+    #[arg(short, long, default_value = "%Y-%m-%d")]
+    format: String,
+
+    /// Print one JSON object per expression
+    #[arg(long)]
+    json: bool,
+
+    /// Print the rules that resolved each expression
+    #[arg(long, conflicts_with = "json")]
+    explain: bool,
+
+    /// Date expressions are relative to, instead of today
+    #[arg(short, long, global = true)]
+    reference: Option<String>,
+
+    /// Time zone deciding what today is: `local`, `utc`, an offset such as
+    /// `+02:00` or a name such as `Europe/Paris`
+    #[arg(long, default_value = "local", global = true)]
+    tz: String,
+
+    /// Language of month names and weekdays: `en`, `fr`, `de` or `es`
+    /// [default: the config file's, else `en`]
+    #[arg(long, global = true)]
+    locale: Option<Locale>,
+}
+
+#[derive(clap::Subcommand)]
+enum Command {
+    /// Rewrite a column of a CSV or TSV file to ISO dates
+    Normalize {
+        /// Header of the column, or its position starting at 1
+        #[arg(short, long)]
+        column: String,
+
+        /// File to read instead of stdin
+        input: Option<PathBuf>,
+
+        /// File to write instead of stdout
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+
+        /// Read and write tab-separated values
+        #[arg(long)]
+        tsv: bool,
+
+        /// Treat the first row as a record instead of headers
+        #[arg(long)]
+        no_headers: bool,
+    },
+}
+
+fn main() -> ExitCode {
+    let args = Args::parse();
+    match run(&args) {
+        Ok(code) => ExitCode::from(code),
+        Err(err) => {
+            eprintln!("temporis: {:#}", err);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn run(args: &Args) -> Result<u8> {
+    if StrftimeItems::new(&args.format).any(|item| matches!(item, Item::Error)) {
+        return Err(anyhow!("Invalid format '{}'", args.format));
+    }
+
+    let mut parser = config()?.parser();
+    if let Some(locale) = args.locale {
+        parser = parser.locale(locale);
+    }
+    let today = today_in(&args.tz)?;
+    let today = match &args.reference {
+        Some(reference) => parser
+            .parse_relative_to(reference, today)
+            .with_context(|| format!("Invalid reference date '{}'", reference))?,
+        None => today,
+    };
+
+    if let Some(Command::Normalize {
+        column,
+        input,
+        output,
+        tsv,
+        no_headers,
+    }) = &args.command
+    {
+        let column = match column.parse::<usize>() {
+            Ok(0) => return Err(anyhow!("Column positions start at 1")),
+            Ok(position) => Column::Index(position - 1),
+            Err(_) => Column::Name(column.clone()),
+        };
+        let input: Box<dyn Read> = match input {
+            Some(path) => Box::new(BufReader::new(
+                File::open(path).with_context(|| format!("Cannot read {}", path.display()))?,
+            )),
+            None => Box::new(io::stdin().lock()),
+        };
+        let output: Box<dyn Write> = match output {
+            Some(path) => Box::new(BufWriter::new(
+                File::create(path).with_context(|| format!("Cannot write {}", path.display()))?,
+            )),
+            None => Box::new(BufWriter::new(io::stdout().lock())),
+        };
+
+        let report = Normalizer::new(column)
+            .delimiter(if *tsv { b'\t' } else { b',' })
+            .has_headers(!no_headers)
+            .reference(today)
+            .parser(parser)
+            .normalize(input, output)?;
+        for error in &report.errors {
+            eprintln!(
+                "temporis: line {}: {}: {}",
+                error.line, error.value, error.error
+            );
+        }
+        return Ok(report
+            .errors
+            .first()
+            .map_or(0, |error| exit_code(&error.error)));
+    }
+
+    // Every expression is printed and the first failure decides the exit code
+    let mut context = ParseContext::new(parser).reference(today);
+    let mut status = 0;
+    let mut report = |expression: &str| {
+        let code = print(args, &mut context, expression);
+        if status == 0 {
+            status = code;
+        }
+    };
+    if !args.expression.is_empty() {
+        args.expression
+            .iter()
+            .for_each(|expression| report(expression));
+    } else {
+        for line in io::stdin().lock().lines() {
+            let line = line?;
+            if !line.trim().is_empty() {
+                report(&line);
+            }
+        }
+    }
+    Ok(status)
+}
+
+/// Prints the date `expression` resolves to, or its error, and returns the
+/// exit code it warrants.
+fn print(args: &Args, context: &mut ParseContext, expression: &str) -> u8 {
+    if args.explain {
+        let explanation = context.explain(expression);
+        print!("{}", explanation);
+        return explanation.result.as_ref().map_or_else(exit_code, |_| 0);
+    }
+    match context.parse(expression) {
+        Ok(date) => {
+            let formatted = date.format(&args.format).to_string();
+            if args.json {
+                println!(
+                    "{}",
+                    serde_json::json!({ "expression": expression, "date": formatted })
+                );
+            } else {
+                println!("{}", formatted);
+            }
+            0
+        }
+        Err(err) => {
+            if args.json {
+                println!(
+                    "{}",
+                    serde_json::json!({ "expression": expression, "error": err.to_string() })
+                );
+            } else {
+                eprintln!("temporis: {}: {}", expression, err);
+            }
+            err.downcast_ref::<ParseError>()
+                .map_or(EXIT_UNRECOGNIZED, exit_code)
+        }
+    }
+}
+
+fn exit_code(err: &ParseError) -> u8 {
+    match err {
+        ParseError::Unrecognized { .. } => EXIT_UNRECOGNIZED,
+        _ => EXIT_INVALID,
+    }
+}
+
+/// Settings from the file named by `TEMPORIS_CONFIG`, else from
+/// `temporis/config.toml` in the XDG config directory when it exists.
+fn config() -> Result<ParserConfig> {
+    if let Some(path) = env::var_os("TEMPORIS_CONFIG") {
+        return ParserConfig::load(path);
+    }
+    let dir = env::var_os("XDG_CONFIG_HOME")
+        .map(PathBuf::from)
+        .filter(|dir| dir.is_absolute())
+        .or_else(|| env::var_os("HOME").map(|home| PathBuf::from(home).join(".config")));
+    match dir.map(|dir| dir.join("temporis").join("config.toml")) {
+        Some(path) if path.exists() => ParserConfig::load(path),
+        _ => Ok(ParserConfig::default()),
+    }
+}
+
+/// Current date in the time zone named by `tz`.
+fn today_in(tz: &str) -> Result<NaiveDate> {
+    let now = Utc::now();
+    match tz.to_lowercase().as_str() {
+        "local" => Ok(Local::now().date_naive()),
+        "utc" | "z" => Ok(now.date_naive()),
+        _ => {
+            if let Ok(offset) = tz.parse::<FixedOffset>() {
+                return Ok(now.with_timezone(&offset).date_naive());
+            }
+            let zone: chrono_tz::Tz = tz
+                .parse()
+                .map_err(|_| anyhow!("Unknown time zone '{}'", tz))?;
+            Ok(now.with_timezone(&zone).date_naive())
+        }
+    }
+}
@@ -0,0 +1,115 @@
+use chrono::{NaiveDate, NaiveDateTime};
+use std::fmt;
+use std::str::FromStr;
+
+use crate::{parse_date, parse_datetime, parse_range, DateRange};
+
+/// Date together with the expression it was parsed from, so `eom` can be
+/// shown back to the user as typed.
+///
+/// ```
+/// use temporis::NaturalDate;
+///
+/// let due: NaturalDate = "16 jan 2024".parse().unwrap();
+/// assert_eq!(due.expr, "16 jan 2024");
+/// assert_eq!(due.to_string(), "2024-01-16");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct NaturalDate {
+    pub expr: String,
+    pub date: NaiveDate,
+}
+
+/// Span of days together with the expression it was parsed from.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct NaturalRange {
+    pub expr: String,
+    pub range: DateRange,
+}
+
+/// Date and time of day together with the expression they were parsed from.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct NaturalDateTime {
+    pub expr: String,
+    pub datetime: NaiveDateTime,
+}
+
+impl NaturalDate {
+    pub fn new(expr: impl Into<String>, date: NaiveDate) -> Self {
+        NaturalDate {
+            expr: expr.into(),
+            date,
+        }
+    }
+}
+
+impl NaturalRange {
+    pub fn new(expr: impl Into<String>, range: DateRange) -> Self {
+        NaturalRange {
+            expr: expr.into(),
+            range,
+        }
+    }
+}
+
+impl NaturalDateTime {
+    pub fn new(expr: impl Into<String>, datetime: NaiveDateTime) -> Self {
+        NaturalDateTime {
+            expr: expr.into(),
+            datetime,
+        }
+    }
+}
+
+/// Parses any expression `parse_date` accepts, relative to today.
+impl FromStr for NaturalDate {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(NaturalDate::new(s.trim(), parse_date(s)?))
+    }
+}
+
+/// Parses any expression `parse_range` accepts, relative to today.
+impl FromStr for NaturalRange {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(NaturalRange::new(s.trim(), parse_range(s)?))
+    }
+}
+
+/// Parses any expression `parse_datetime` accepts, relative to now.
+impl FromStr for NaturalDateTime {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(NaturalDateTime::new(s.trim(), parse_datetime(s)?))
+    }
+}
+
+/// Writes the resolved date as `2024-01-16`.
+impl fmt::Display for NaturalDate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.date.format("%Y-%m-%d"))
+    }
+}
+
+/// Writes the resolved range as `2024-03-01..2024-03-31`.
+impl fmt::Display for NaturalRange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}..{}",
+            self.range.start.format("%Y-%m-%d"),
+            self.range.end.format("%Y-%m-%d")
+        )
+    }
+}
+
+/// Writes the resolved date and time as `2024-01-16 14:30:00`.
+impl fmt::Display for NaturalDateTime {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.datetime.format("%Y-%m-%d %H:%M:%S"))
+    }
+}
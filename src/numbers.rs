@@ -0,0 +1,440 @@
+//! Number words (`three days`, `twenty-first`, `a couple of weeks`) rewritten
+//! into the digits the grammar reads, before the locale translates the rest.
+
+use std::borrow::Cow;
+
+use crate::complete::ordinal_suffix;
+use crate::duration::Unit;
+use crate::{Locale, Parser};
+
+/// Number words of a language. Cardinals of 100 and more multiply what comes
+/// before them (`two hundred`); ordinals are stems that take one of `endings`
+/// (German `erst` + `en`).
+struct NumberWords {
+    cardinals: &'static [(&'static str, u32)],
+    ordinals: &'static [(&'static str, u32)],
+    endings: &'static [&'static str],
+    /// Words that read as one at the start of a number (`a week`).
+    articles: &'static [&'static str],
+    /// Word joining the parts of a number (`thirty-one`, `vingt et un`).
+    joiner: &'static str,
+    /// Whether the joiner also appears inside words (German `einundzwanzig`).
+    compounds: bool,
+    half: &'static [&'static str],
+    couple: &'static [&'static str],
+    few: &'static [&'static str],
+    /// Word after `couple` or `few` (`a couple of days`).
+    of: &'static str,
+    fortnight: &'static [&'static str],
+}
+
+#[rustfmt::skip]
+const ENGLISH: NumberWords = NumberWords {
+    cardinals: &[
+        ("zero", 0), ("one", 1), ("two", 2), ("three", 3), ("four", 4), ("five", 5), ("six", 6),
+        ("seven", 7), ("eight", 8), ("nine", 9), ("ten", 10), ("eleven", 11), ("twelve", 12),
+        ("thirteen", 13), ("fourteen", 14), ("fifteen", 15), ("sixteen", 16), ("seventeen", 17),
+        ("eighteen", 18), ("nineteen", 19), ("twenty", 20), ("thirty", 30), ("forty", 40),
+        ("fifty", 50), ("sixty", 60), ("seventy", 70), ("eighty", 80), ("ninety", 90),
+        ("hundred", 100), ("thousand", 1000),
+    ],
+    ordinals: &[
+        ("first", 1), ("second", 2), ("third", 3), ("fourth", 4), ("fifth", 5), ("sixth", 6),
+        ("seventh", 7), ("eighth", 8), ("ninth", 9), ("tenth", 10), ("eleventh", 11),
+        ("twelfth", 12), ("thirteenth", 13), ("fourteenth", 14), ("fifteenth", 15),
+        ("sixteenth", 16), ("seventeenth", 17), ("eighteenth", 18), ("nineteenth", 19),
+        ("twentieth", 20), ("thirtieth", 30), ("fortieth", 40), ("fiftieth", 50),
+        ("sixtieth", 60), ("seventieth", 70), ("eightieth", 80), ("ninetieth", 90),
+        ("hundredth", 100), ("thousandth", 1000),
+    ],
+    endings: &[""],
+    articles: &["a", "an"],
+    joiner: "and",
+    compounds: false,
+    half: &["half"],
+    couple: &["couple"],
+    few: &["few"],
+    of: "of",
+    fortnight: &["fortnight", "fortnights"],
+};
+
+#[rustfmt::skip]
+const FRENCH: NumberWords = NumberWords {
+    cardinals: &[
+        ("zéro", 0), ("zero", 0), ("un", 1), ("une", 1), ("deux", 2), ("trois", 3), ("quatre", 4),
+        ("cinq", 5), ("six", 6), ("sept", 7), ("huit", 8), ("neuf", 9), ("dix", 10), ("onze", 11),
+        ("douze", 12), ("treize", 13), ("quatorze", 14), ("quinze", 15), ("seize", 16),
+        ("vingt", 20), ("trente", 30), ("quarante", 40), ("cinquante", 50), ("soixante", 60),
+        ("cent", 100), ("cents", 100), ("mille", 1000),
+    ],
+    ordinals: &[
+        ("premier", 1), ("première", 1), ("premiere", 1), ("unième", 1), ("unieme", 1),
+        ("deuxième", 2), ("deuxieme", 2), ("second", 2), ("seconde", 2),
+        ("troisième", 3), ("troisieme", 3), ("quatrième", 4), ("quatrieme", 4),
+        ("cinquième", 5), ("cinquieme", 5), ("sixième", 6), ("sixieme", 6),
+        ("septième", 7), ("septieme", 7), ("huitième", 8), ("huitieme", 8),
+        ("neuvième", 9), ("neuvieme", 9), ("dixième", 10), ("dixieme", 10),
+        ("onzième", 11), ("onzieme", 11), ("douzième", 12), ("douzieme", 12),
+        ("treizième", 13), ("treizieme", 13), ("quatorzième", 14), ("quatorzieme", 14),
+        ("quinzième", 15), ("quinzieme", 15), ("seizième", 16), ("seizieme", 16),
+        ("vingtième", 20), ("vingtieme", 20), ("trentième", 30), ("trentieme", 30),
+    ],
+    endings: &[""],
+    articles: &["un", "une"],
+    joiner: "et",
+    compounds: false,
+    half: &["demi", "demie"],
+    couple: &[],
+    few: &["quelques"],
+    of: "de",
+    fortnight: &[],
+};
+
+#[rustfmt::skip]
+const GERMAN: NumberWords = NumberWords {
+    cardinals: &[
+        ("null", 0), ("eins", 1), ("ein", 1), ("eine", 1), ("einen", 1), ("zwei", 2), ("drei", 3),
+        ("vier", 4), ("fünf", 5), ("fuenf", 5), ("sechs", 6), ("sieben", 7), ("acht", 8),
+        ("neun", 9), ("zehn", 10), ("elf", 11), ("zwölf", 12), ("zwoelf", 12), ("dreizehn", 13),
+        ("vierzehn", 14), ("fünfzehn", 15), ("fuenfzehn", 15), ("sechzehn", 16),
+        ("siebzehn", 17), ("achtzehn", 18), ("neunzehn", 19), ("zwanzig", 20), ("dreißig", 30),
+        ("dreissig", 30), ("vierzig", 40), ("fünfzig", 50), ("fuenfzig", 50), ("sechzig", 60),
+        ("siebzig", 70), ("achtzig", 80), ("neunzig", 90), ("hundert", 100), ("tausend", 1000),
+    ],
+    ordinals: &[
+        ("erst", 1), ("zweit", 2), ("dritt", 3), ("viert", 4), ("fünft", 5), ("fuenft", 5),
+        ("sechst", 6), ("siebt", 7), ("acht", 8), ("neunt", 9), ("zehnt", 10), ("elft", 11),
+        ("zwölft", 12), ("zwoelft", 12), ("dreizehnt", 13), ("vierzehnt", 14),
+        ("fünfzehnt", 15), ("fuenfzehnt", 15), ("sechzehnt", 16), ("siebzehnt", 17),
+        ("achtzehnt", 18), ("neunzehnt", 19), ("zwanzigst", 20), ("dreißigst", 30),
+        ("dreissigst", 30),
+    ],
+    endings: &["e", "en", "er", "es", "em"],
+    articles: &["ein", "eine", "einen", "einem", "einer"],
+    joiner: "und",
+    compounds: true,
+    half: &["halb", "halbe", "halben", "halber", "halbes"],
+    couple: &["paar"],
+    few: &["einige"],
+    of: "",
+    fortnight: &[],
+};
+
+#[rustfmt::skip]
+const SPANISH: NumberWords = NumberWords {
+    cardinals: &[
+        ("cero", 0), ("uno", 1), ("una", 1), ("un", 1), ("dos", 2), ("tres", 3), ("cuatro", 4),
+        ("cinco", 5), ("seis", 6), ("siete", 7), ("ocho", 8), ("nueve", 9), ("diez", 10),
+        ("once", 11), ("doce", 12), ("trece", 13), ("catorce", 14), ("quince", 15),
+        ("dieciséis", 16), ("dieciseis", 16), ("diecisiete", 17), ("dieciocho", 18),
+        ("diecinueve", 19), ("veinte", 20), ("veintiuno", 21), ("veintiuna", 21), ("veintiún", 21),
+        ("veintidós", 22), ("veintidos", 22), ("veintitrés", 23), ("veintitres", 23),
+        ("veinticuatro", 24), ("veinticinco", 25), ("veintiséis", 26), ("veintiseis", 26),
+        ("veintisiete", 27), ("veintiocho", 28), ("veintinueve", 29), ("treinta", 30),
+        ("cuarenta", 40), ("cincuenta", 50), ("sesenta", 60), ("setenta", 70), ("ochenta", 80),
+        ("noventa", 90), ("cien", 100), ("ciento", 100), ("mil", 1000),
+    ],
+    ordinals: &[
+        ("primer", 1), ("segund", 2), ("tercer", 3), ("cuart", 4), ("quint", 5), ("sext", 6),
+        ("séptim", 7), ("septim", 7), ("octav", 8), ("noven", 9), ("décim", 10), ("decim", 10),
+    ],
+    endings: &["", "o", "a"],
+    articles: &["un", "una", "uno"],
+    joiner: "y",
+    compounds: false,
+    half: &["medio", "media"],
+    couple: &["par"],
+    few: &["algunos", "algunas"],
+    of: "de",
+    fortnight: &[],
+};
+
+/// Value of a number read from words.
+enum Amount {
+    Whole(u32),
+    /// A whole number and a half (`one and a half`, `half a`).
+    Half(u32),
+    Ordinal(u32),
+}
+
+impl NumberWords {
+    fn of(locale: Locale) -> &'static NumberWords {
+        match locale {
+            Locale::English => &ENGLISH,
+            Locale::French => &FRENCH,
+            Locale::German => &GERMAN,
+            Locale::Spanish => &SPANISH,
+        }
+    }
+
+    /// Whether `word` may start or belong to a number, so that inputs without
+    /// any such word are left alone without splitting them.
+    fn knows(&self, word: &str) -> bool {
+        self.value(word).is_some()
+            || (self.compounds && word.contains(self.joiner))
+            || [
+                self.articles,
+                self.half,
+                self.couple,
+                self.few,
+                self.fortnight,
+            ]
+            .iter()
+            .any(|words| words.contains(&word))
+    }
+
+    /// Value of a single number word, and whether it is an ordinal.
+    fn value(&self, word: &str) -> Option<(u32, bool)> {
+        if let Some((_, value)) = self.cardinals.iter().find(|(name, _)| *name == word) {
+            return Some((*value, false));
+        }
+        self.ordinals
+            .iter()
+            .find(|(stem, _)| {
+                word.strip_prefix(stem)
+                    .is_some_and(|ending| self.endings.contains(&ending))
+            })
+            .map(|(_, value)| (*value, true))
+    }
+
+    /// Spans of the words of `input` (`3d` and `one_off` are words of their
+    /// own), with German compounds such as `einundzwanzig` split around their
+    /// joiner.
+    fn words(&self, input: &str) -> Vec<(usize, usize)> {
+        let is_word = |c: char| c.is_alphanumeric() || c == '_';
+        let mut words = Vec::new();
+        let mut rest = 0;
+        while let Some(start) = input[rest..].find(is_word).map(|n| rest + n) {
+            let end = input[start..]
+                .find(|c: char| !is_word(c))
+                .map_or(input.len(), |n| start + n);
+            let word = &input[start..end];
+            let compound = match self.compounds {
+                true => word
+                    .match_indices(self.joiner)
+                    .map(|(at, _)| (&word[..at], &word[at + self.joiner.len()..]))
+                    .find(|(left, right)| {
+                        self.value(left).is_some() && self.value(right).is_some()
+                    }),
+                false => None,
+            };
+            match compound {
+                Some((left, _)) => {
+                    let right = start + left.len() + self.joiner.len();
+                    words.extend([
+                        (start, start + left.len()),
+                        (start + left.len(), right),
+                        (right, end),
+                    ]);
+                }
+                None => words.push((start, end)),
+            }
+            rest = end;
+        }
+        words
+    }
+}
+
+/// Reads the number words starting at one word of an input.
+struct Reader<'a> {
+    input: &'a str,
+    spans: &'a [(usize, usize)],
+    start: usize,
+    words: &'static NumberWords,
+}
+
+impl Reader<'_> {
+    /// The word at `index`, as long as only spaces or hyphens separate it
+    /// from the start.
+    fn word(&self, index: usize) -> Option<&str> {
+        let &(start, end) = self.spans.get(index)?;
+        let joined = self.spans[self.start..=index].windows(2).all(|pair| {
+            let gap = &self.input[pair[0].1..pair[1].0];
+            gap == "-" || gap.chars().all(char::is_whitespace)
+        });
+        joined.then(|| &self.input[start..end])
+    }
+
+    fn is(&self, index: usize, words: &[&str]) -> bool {
+        self.word(index).is_some_and(|word| words.contains(&word))
+    }
+
+    /// Digits replacing the words from the start, and the index of the last
+    /// word they replace.
+    fn read(&self, couple: u32, few: u32) -> Option<(String, usize)> {
+        let words = self.words;
+        let first = self.start + usize::from(self.is(self.start, words.articles));
+        // A couple of, a few
+        for (names, amount) in [(words.couple, couple), (words.few, few)] {
+            if self.is(first, names) {
+                let of = !words.of.is_empty() && self.is(first + 1, &[words.of]);
+                return Some((amount.to_string(), first + usize::from(of)));
+            }
+        }
+        // Half a
+        if self.is(first, words.half) {
+            let article = self.is(first + 1, words.articles);
+            return Some((format_amount(Amount::Half(0)), first + usize::from(article)));
+        }
+        if self.is(self.start, words.fortnight) {
+            return Some(("2 weeks".to_string(), self.start));
+        }
+
+        let (amount, mut last) = self.number()?;
+        let amount = match amount {
+            Amount::Whole(value) => {
+                // One and a half
+                let joiner = usize::from(self.is(last + 1, &[words.joiner]));
+                let article = usize::from(self.is(last + 1 + joiner, words.articles));
+                if self.is(last + 1 + joiner + article, words.half) {
+                    last += 1 + joiner + article;
+                    Amount::Half(value)
+                } else if self.is(last + 1, words.fortnight) {
+                    return Some((format!("{} weeks", value.saturating_mul(2)), last + 1));
+                } else {
+                    Amount::Whole(value)
+                }
+            }
+            amount => amount,
+        };
+        Some((format_amount(amount), last))
+    }
+
+    /// Number spelled from the start (`twenty-one`, `two hundred and five`,
+    /// `a thousand`), and the index of its last word.
+    fn number(&self) -> Option<(Amount, usize)> {
+        let words = self.words;
+        let (mut total, mut current, mut last) = (0u32, 0u32, 0u32);
+        let (mut end, mut joined) = (None, false);
+        let mut index = self.start;
+        while let Some(word) = self.word(index) {
+            if end.is_some() && !joined && word == words.joiner {
+                joined = true;
+                index += 1;
+                continue;
+            }
+            let (value, ordinal) = match words.value(word) {
+                Some(value) => value,
+                None if index == self.start && words.articles.contains(&word) => (1, false),
+                None => break,
+            };
+            if value >= 1000 {
+                if total > 0 || (end.is_some() && last == 0) {
+                    break;
+                }
+                total = current.max(1) * value;
+                current = 0;
+                last = 0;
+            } else if value >= 100 {
+                if current >= 100 || (end.is_some() && last == 0) {
+                    break;
+                }
+                current = current.max(1) * value;
+                last = 0;
+            } else {
+                // Tens take a smaller number after them (`twenty-one`,
+                // `soixante-dix`) or, joined, a unit before them (German
+                // `einundzwanzig`)
+                let fits = last == 0
+                    || (last % 10 == 0 && value < last)
+                    || (joined && last < 10 && value >= 20 && value % 10 == 0);
+                if !fits {
+                    break;
+                }
+                current += value;
+                last = value;
+            }
+            end = Some(index);
+            joined = false;
+            index += 1;
+            if ordinal {
+                return Some((Amount::Ordinal(total + current), index - 1));
+            }
+        }
+        // A lone article needs something to count (`a week`)
+        let end = end.filter(|&end| {
+            end > self.start
+                || self
+                    .word(end)
+                    .is_some_and(|word| words.value(word).is_some())
+                || self.word(end + 1).is_some()
+        })?;
+        Some((Amount::Whole(total + current), end))
+    }
+}
+
+fn format_amount(amount: Amount) -> String {
+    match amount {
+        Amount::Whole(value) => value.to_string(),
+        Amount::Half(value) => format!("{}.5", value),
+        Amount::Ordinal(value) => {
+            let digits = value.to_string();
+            format!("{}{}", digits, ordinal_suffix(&digits))
+        }
+    }
+}
+
+impl Parser {
+    /// Rewrites a normalized input into the English the grammar reads: number
+    /// words into digits, then localized words into English.
+    pub(crate) fn rewrite<'a>(&self, input: &'a str) -> Cow<'a, str> {
+        match self.read_numbers(input) {
+            Cow::Borrowed(input) => self.locale.translate(input),
+            Cow::Owned(input) => Cow::Owned(self.locale.translate(&input).into_owned()),
+        }
+    }
+
+    /// Rewrites the number words of a lowercase `input` into digits: `three
+    /// days` is `3 days` and `twenty-first` is `21st`.
+    pub(crate) fn read_numbers<'a>(&self, input: &'a str) -> Cow<'a, str> {
+        let words = NumberWords::of(self.locale);
+        if !input
+            .split(|c: char| !c.is_alphanumeric() && c != '_')
+            .any(|word| words.knows(word))
+        {
+            return Cow::Borrowed(input);
+        }
+        let spans = words.words(input);
+        let mut output = String::new();
+        let mut copied = 0;
+        let mut index = 0;
+        while index < spans.len() {
+            let reader = Reader {
+                input,
+                spans: &spans,
+                start: index,
+                words,
+            };
+            match reader.read(self.couple, self.few) {
+                // A word the locale already reads (French `sept` for
+                // September) is a number only before a unit (`sept jours`,
+                // but `16 sept 2024`)
+                Some((_, last))
+                    if last == index
+                        && reader
+                            .word(index)
+                            .is_some_and(|word| self.locale.translates(word))
+                        && reader
+                            .word(index + 1)
+                            .and_then(|word| self.locale.translation(word))
+                            .and_then(Unit::parse)
+                            .is_none() =>
+                {
+                    index += 1;
+                }
+                Some((digits, last)) => {
+                    output.push_str(&input[copied..spans[index].0]);
+                    output.push_str(&digits);
+                    copied = spans[last].1;
+                    index = last + 1;
+                }
+                None => index += 1,
+            }
+        }
+        if copied == 0 {
+            return Cow::Borrowed(input);
+        }
+        output.push_str(&input[copied..]);
+        Cow::Owned(output)
+    }
+}
@@ -0,0 +1,113 @@
+use anyhow::anyhow;
+use chrono::{Local, NaiveDate};
+
+use crate::{normalize, Interval, Parser, Resolved};
+
+/// Condition on a date read from a filter such as `before:eow`, so that every
+/// tool filters dates the same way.
+///
+/// A filter is a comparator followed by `:` or a space and an expression:
+/// `before`, `after`, `on`, `by`, `within` or `between X and Y`. An attribute
+/// in front of the comparator (`due.before:eow`) is skipped; the caller picks
+/// the date it applies to.
+///
+/// ```
+/// use chrono::NaiveDate;
+/// use temporis::{DatePredicate, Parser};
+///
+/// let today = NaiveDate::from_ymd_opt(2024, 1, 16).unwrap();
+/// let due = Parser::new().parse_predicate_relative_to("before:eow", today).unwrap();
+/// assert!(due.matches(NaiveDate::from_ymd_opt(2024, 1, 20).unwrap()));
+/// assert!(!due.matches(NaiveDate::from_ymd_opt(2024, 1, 21).unwrap()));
+///
+/// let filter = Parser::new().parse_predicate_relative_to("due.before:eow", today).unwrap();
+/// assert_eq!(filter, due);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DatePredicate {
+    /// Strictly before the first day of the expression.
+    Before(Resolved),
+    /// Strictly after the last day of the expression.
+    After(Resolved),
+    /// Any day the expression covers (`on:fri`, `on:march`).
+    On(Interval),
+    /// On or before the last day of the expression.
+    By(Resolved),
+    /// From today through the expression (`within:2w`), or from the
+    /// expression through today when it is in the past.
+    Within(Interval),
+    /// From the first day of one expression through the last day of another.
+    Between(Interval),
+}
+
+impl DatePredicate {
+    pub fn matches(&self, date: NaiveDate) -> bool {
+        let date = Resolved::Date(date);
+        match self {
+            DatePredicate::Before(start) => date < *start,
+            DatePredicate::After(end) => date > *end,
+            DatePredicate::By(end) => date <= *end,
+            DatePredicate::On(interval)
+            | DatePredicate::Within(interval)
+            | DatePredicate::Between(interval) => interval.start <= date && date <= interval.end,
+        }
+    }
+}
+
+impl Parser {
+    /// Parses a filter such as `after:2024-01-01`, relative to today.
+    pub fn parse_predicate(&self, filter: &str) -> Result<DatePredicate, anyhow::Error> {
+        self.parse_predicate_relative_to(filter, Local::now().date_naive())
+    }
+
+    /// Parses a filter such as `after:2024-01-01`, relative to the given
+    /// reference date. Expressions may be periods or open intervals
+    /// (`on:..eom`); errors in them keep their `ParseError`.
+    pub fn parse_predicate_relative_to(
+        &self,
+        filter: &str,
+        today: NaiveDate,
+    ) -> Result<DatePredicate, anyhow::Error> {
+        let filter = normalize(filter);
+        let (comparator, expr) = filter
+            .split_once(|c: char| c == ':' || c.is_whitespace())
+            .ok_or_else(|| anyhow!("Missing date after '{}'", filter))?;
+        let comparator = match comparator.split_once('.') {
+            Some((attribute, comparator)) if !attribute.is_empty() => comparator,
+            _ => comparator,
+        };
+        let interval = |expr: &str| match expr.trim() {
+            "" => Err(anyhow!("Missing date after '{}'", comparator)),
+            expr => self.parse_interval_relative_to(expr, today),
+        };
+
+        let predicate = match comparator {
+            "before" => DatePredicate::Before(interval(expr)?.start),
+            "after" => DatePredicate::After(interval(expr)?.end),
+            "on" => DatePredicate::On(interval(expr)?),
+            "by" => DatePredicate::By(interval(expr)?.end),
+            "within" => {
+                let (today, end) = (Resolved::Date(today), interval(expr)?.end);
+                DatePredicate::Within(Interval::new(today.min(end), today.max(end)))
+            }
+            "between" => {
+                let (start, end) = expr
+                    .split_once(" and ")
+                    .ok_or_else(|| anyhow!("Expected 'between <date> and <date>'"))?;
+                DatePredicate::Between(Interval::new(interval(start)?.start, interval(end)?.end))
+            }
+            _ => {
+                return Err(anyhow!(
+                    "Unknown comparator '{}', expected before, after, on, by, within or between",
+                    comparator
+                ))
+            }
+        };
+        Ok(predicate)
+    }
+}
+
+/// Parses a filter such as `before:eow`, relative to today.
+pub fn parse_predicate(filter: &str) -> Result<DatePredicate, anyhow::Error> {
+    Parser::default().parse_predicate(filter)
+}
@@ -0,0 +1,86 @@
+use chrono::NaiveDate;
+
+/// Inclusive span of days produced by `parse_range`.
+///
+/// Periods such as `march` or `2025` cover every day in them; any other
+/// expression yields a range of a single day.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct DateRange {
+    pub start: NaiveDate,
+    pub end: NaiveDate,
+}
+
+impl DateRange {
+    pub fn new(start: NaiveDate, end: NaiveDate) -> Self {
+        DateRange { start, end }
+    }
+
+    /// Range covering a single day.
+    pub fn day(date: NaiveDate) -> Self {
+        DateRange::new(date, date)
+    }
+
+    pub fn contains(&self, date: NaiveDate) -> bool {
+        self.start <= date && date <= self.end
+    }
+}
+
+/// Date an expression stands for, or a sentinel no date stands for.
+///
+/// Sentinels order around every date: `Always` before the earliest and
+/// `Never` after the latest, so `later` sorts last.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Resolved {
+    /// Already reached on any day: the open start of `..eom`.
+    Always,
+    Date(NaiveDate),
+    /// Not reached on any day: `later`, `someday` and the open end of
+    /// `2024-01-01..`.
+    Never,
+}
+
+impl Resolved {
+    /// The date, unless this is a sentinel.
+    pub fn date(self) -> Option<NaiveDate> {
+        match self {
+            Resolved::Date(date) => Some(date),
+            _ => None,
+        }
+    }
+}
+
+/// Span of days produced by `parse_interval`, either end of which may be open.
+///
+/// ```
+/// use chrono::NaiveDate;
+/// use temporis::{Interval, Parser, Resolved};
+///
+/// let today = NaiveDate::from_ymd_opt(2024, 1, 16).unwrap();
+/// let interval = Parser::new().parse_interval_relative_to("..eom", today).unwrap();
+/// assert_eq!(interval.start, Resolved::Always);
+/// assert!(interval.contains(NaiveDate::from_ymd_opt(1999, 1, 1).unwrap()));
+/// assert!(!interval.contains(NaiveDate::from_ymd_opt(2024, 2, 1).unwrap()));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Interval {
+    pub start: Resolved,
+    pub end: Resolved,
+}
+
+impl Interval {
+    pub fn new(start: Resolved, end: Resolved) -> Self {
+        Interval { start, end }
+    }
+
+    /// Whether `date` lies between the ends, inclusive. An interval ending
+    /// before it starts contains no day.
+    pub fn contains(&self, date: NaiveDate) -> bool {
+        self.start <= Resolved::Date(date) && Resolved::Date(date) <= self.end
+    }
+}
+
+impl From<DateRange> for Interval {
+    fn from(range: DateRange) -> Self {
+        Interval::new(Resolved::Date(range.start), Resolved::Date(range.end))
+    }
+}
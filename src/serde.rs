@@ -0,0 +1,118 @@
+//! Adapters for date fields written as natural expressions.
+//!
+//! ```
+//! use chrono::NaiveDate;
+//! use serde::Deserialize;
+//!
+//! #[derive(Deserialize)]
+//! struct Task {
+//!     #[serde(with = "temporis::serde::natural")]
+//!     due: NaiveDate,
+//! }
+//!
+//! let task: Task = serde_json::from_str(r#"{ "due": "2024-01-16" }"#).unwrap();
+//! let task: Task = serde_json::from_str(r#"{ "due": "eom" }"#).unwrap();
+//! ```
+
+use ::serde::de::{self, Deserializer, MapAccess, Visitor};
+use ::serde::ser::{SerializeStruct, Serializer};
+use ::serde::{Deserialize, Serialize};
+use chrono::NaiveDate;
+use std::fmt;
+
+use crate::{parse_date, NaturalDate};
+
+/// Serializes a `NaiveDate` as an ISO date and deserializes it from any
+/// expression `parse_date` accepts.
+pub mod natural {
+    use super::*;
+
+    pub fn serialize<S: Serializer>(date: &NaiveDate, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(&date.format("%Y-%m-%d"))
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<NaiveDate, D::Error> {
+        let expr = String::deserialize(deserializer)?;
+        parse::<D::Error>(&expr)
+    }
+}
+
+/// Same as `natural`, for `Option<NaiveDate>` fields.
+pub mod natural_option {
+    use super::*;
+
+    pub fn serialize<S: Serializer>(
+        date: &Option<NaiveDate>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        match date {
+            Some(date) => serializer.collect_str(&date.format("%Y-%m-%d")),
+            None => serializer.serialize_none(),
+        }
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Option<NaiveDate>, D::Error> {
+        match Option::<String>::deserialize(deserializer)? {
+            Some(expr) => parse::<D::Error>(&expr).map(Some),
+            None => Ok(None),
+        }
+    }
+}
+
+fn parse<E: de::Error>(expr: &str) -> Result<NaiveDate, E> {
+    parse_date(expr).map_err(|err| E::custom(format_args!("'{}': {}", expr, err)))
+}
+
+/// Written as `{ "expr": "eom", "date": "2024-01-31" }`.
+impl Serialize for NaturalDate {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut state = serializer.serialize_struct("NaturalDate", 2)?;
+        state.serialize_field("expr", &self.expr)?;
+        state.serialize_field("date", &self.date.format("%Y-%m-%d").to_string())?;
+        state.end()
+    }
+}
+
+/// Read from a bare expression, resolved relative to today, or from the
+/// serialized form, whose date is kept as is.
+impl<'de> Deserialize<'de> for NaturalDate {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_any(NaturalDateVisitor)
+    }
+}
+
+struct NaturalDateVisitor;
+
+impl<'de> Visitor<'de> for NaturalDateVisitor {
+    type Value = NaturalDate;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a date expression or a map with `expr` and `date`")
+    }
+
+    fn visit_str<E: de::Error>(self, expr: &str) -> Result<NaturalDate, E> {
+        Ok(NaturalDate::new(expr, parse::<E>(expr)?))
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<NaturalDate, A::Error> {
+        let mut expr: Option<String> = None;
+        let mut date: Option<String> = None;
+        while let Some(key) = map.next_key::<String>()? {
+            match key.as_str() {
+                "expr" => expr = Some(map.next_value()?),
+                "date" => date = Some(map.next_value()?),
+                _ => return Err(de::Error::unknown_field(&key, &["expr", "date"])),
+            }
+        }
+        let expr = expr.ok_or_else(|| de::Error::missing_field("expr"))?;
+        let date = match date {
+            Some(date) => NaiveDate::parse_from_str(&date, "%Y-%m-%d").map_err(|_| {
+                de::Error::invalid_value(de::Unexpected::Str(&date), &"an ISO date")
+            })?,
+            None => parse::<A::Error>(&expr)?,
+        };
+        Ok(NaturalDate::new(expr, date))
+    }
+}
@@ -0,0 +1,156 @@
+use std::collections::HashSet;
+
+/// Candidate rewrite of an input with its unknown words replaced.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct Correction {
+    pub text: String,
+    /// Largest edit distance among the replaced words.
+    pub max_distance: usize,
+    total_distance: usize,
+}
+
+/// Most candidates kept for each unknown word.
+const CANDIDATES_PER_WORD: usize = 5;
+
+/// Inputs with more unknown words than this are not worth correcting.
+const MAX_UNKNOWN_WORDS: usize = 3;
+
+/// Rewrites `input` by replacing every word that is neither a keyword nor a
+/// connective with close keywords, closest first. Words match when they are a
+/// few edits away or a prefix of the keyword (`wednes` for `wednesday`).
+pub(crate) fn corrections(input: &str, keywords: &[&str], connectives: &[&str]) -> Vec<Correction> {
+    let known: HashSet<&str> = keywords.iter().chain(connectives).copied().collect();
+    let unknown: Vec<(usize, &str)> = words(input)
+        .filter(|(_, word)| !known.contains(word))
+        .collect();
+    if unknown.is_empty() || unknown.len() > MAX_UNKNOWN_WORDS {
+        return Vec::new();
+    }
+
+    // A word longer than every keyword plus the edits allowed is neither
+    // close to one nor a prefix of one
+    let longest = keywords
+        .iter()
+        .chain(connectives)
+        .map(|keyword| keyword.chars().count())
+        .max()
+        .unwrap_or(0);
+    if unknown
+        .iter()
+        .any(|(_, word)| word.chars().count() > longest + max_distance(word))
+    {
+        return Vec::new();
+    }
+
+    let mut candidates = Vec::with_capacity(unknown.len());
+    for (_, word) in &unknown {
+        let mut close: Vec<(usize, &str)> = keywords
+            .iter()
+            .chain(connectives)
+            .filter_map(|keyword| {
+                let distance = edit_distance(word, keyword);
+                let is_prefix = word.chars().count() >= 3 && keyword.starts_with(word);
+                (distance <= max_distance(word) || is_prefix).then_some((distance, *keyword))
+            })
+            .collect();
+        if close.is_empty() {
+            return Vec::new();
+        }
+        // Stable sort keeps the keyword order among equally close words
+        close.sort_by_key(|(distance, _)| *distance);
+        close.truncate(CANDIDATES_PER_WORD);
+        candidates.push(close);
+    }
+
+    // Every word takes its best candidate, then each word in turn tries its
+    // alternatives while the others keep their best
+    let best: Vec<usize> = vec![0; unknown.len()];
+    let mut choices = vec![best.clone()];
+    for (word, close) in candidates.iter().enumerate() {
+        for alternative in 1..close.len() {
+            let mut choice = best.clone();
+            choice[word] = alternative;
+            choices.push(choice);
+        }
+    }
+
+    let mut corrections: Vec<Correction> = choices
+        .into_iter()
+        .map(|choice| {
+            let mut text = String::with_capacity(input.len());
+            let mut copied = 0;
+            let mut max_distance = 0;
+            let mut total_distance = 0;
+            for (((start, word), close), index) in unknown.iter().zip(&candidates).zip(choice) {
+                let (distance, keyword) = close[index];
+                text.push_str(&input[copied..*start]);
+                text.push_str(keyword);
+                copied = start + word.len();
+                max_distance = max_distance.max(distance);
+                total_distance += distance;
+            }
+            text.push_str(&input[copied..]);
+            Correction {
+                text,
+                max_distance,
+                total_distance,
+            }
+        })
+        .collect();
+    corrections.sort_by_key(|correction| correction.total_distance);
+    corrections.dedup_by(|a, b| a.text == b.text);
+    corrections
+}
+
+/// Runs of letters in `input`, with their byte offsets.
+fn words(input: &str) -> impl Iterator<Item = (usize, &str)> {
+    let mut rest = input.char_indices().peekable();
+    std::iter::from_fn(move || {
+        let (start, _) = rest.find(|(_, c)| c.is_alphabetic())?;
+        let mut end = input.len();
+        while let Some((index, c)) = rest.peek() {
+            if !c.is_alphabetic() {
+                end = *index;
+                break;
+            }
+            rest.next();
+        }
+        Some((start, &input[start..end]))
+    })
+}
+
+/// Largest edit distance still considered a typo of `word`.
+fn max_distance(word: &str) -> usize {
+    match word.chars().count() {
+        0..=2 => 0,
+        3..=5 => 1,
+        _ => 2,
+    }
+}
+
+/// Optimal string alignment distance: insertions, deletions, substitutions
+/// and transpositions of adjacent characters each cost one edit.
+fn edit_distance(a: &str, b: &str) -> usize {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    let mut rows = vec![vec![0; b.len() + 1]; a.len() + 1];
+    for (i, row) in rows.iter_mut().enumerate() {
+        row[0] = i;
+    }
+    for (j, cell) in rows[0].iter_mut().enumerate() {
+        *cell = j;
+    }
+    for i in 1..=a.len() {
+        for j in 1..=b.len() {
+            let cost = usize::from(a[i - 1] != b[j - 1]);
+            let mut distance = (rows[i - 1][j] + 1)
+                .min(rows[i][j - 1] + 1)
+                .min(rows[i - 1][j - 1] + cost);
+            if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+                distance = distance.min(rows[i - 2][j - 2] + 1);
+            }
+            rows[i][j] = distance;
+        }
+    }
+    rows[a.len()][b.len()]
+}
@@ -0,0 +1,140 @@
+//! Named dates of Taskwarrior, for `Parser::taskwarrior`.
+//!
+//! Weeks start on Monday, as with Taskwarrior's `weekstart=monday`.
+
+use chrono::{Datelike, NaiveDate, NaiveDateTime, NaiveTime, Weekday};
+
+use crate::duration::{self, CalendarDuration};
+use crate::{add_days, month_number, Marker, ParseError};
+
+/// Named dates only Taskwarrior mode reads, for completion and suggestions.
+#[rustfmt::skip]
+pub(crate) const NAMED_DATES: &[&str] = &[
+    "sod", "eod", "sopd", "eopd", "sond", "eond", "socd", "eocd",
+    "later", "someday",
+    "easter", "eastermonday", "goodfriday", "ascension", "pentecost",
+    "midsommar", "midsommarafton",
+];
+
+/// Date Taskwarrior gives `later` and `someday`.
+const LATER: NaiveDate = match NaiveDate::from_ymd_opt(9999, 12, 30) {
+    Some(date) => date,
+    None => panic!("LATER is not a valid date"),
+};
+
+/// Time of the dates ending a period.
+const END_OF_DAY: NaiveTime = match NaiveTime::from_hms_opt(23, 59, 59) {
+    Some(time) => time,
+    None => panic!("END_OF_DAY is not a valid time"),
+};
+
+/// Date of a Taskwarrior named date, month name or ISO-8601 duration.
+pub(crate) fn keyword(input: &str, today: NaiveDate) -> Result<Option<NaiveDate>, anyhow::Error> {
+    // Markers without a direction are the current period (`sow` is this
+    // week's Monday)
+    if let Some(marker) = Marker::parse(input) {
+        return marker.resolve(today).map(Some);
+    }
+    if let Some(duration) = iso_duration(input) {
+        return duration
+            .add_to(today)
+            .map(Some)
+            .ok_or_else(|| ParseError::OutOfRange.into());
+    }
+    // Month names are the 1st of the month, next year once it has begun
+    if let Some(month) = month_number(input) {
+        let year = today.year() + i32::from(month <= today.month());
+        return NaiveDate::from_ymd_opt(year, month, 1)
+            .map(Some)
+            .ok_or_else(|| ParseError::OutOfRange.into());
+    }
+
+    let date = match input {
+        "later" | "someday" => LATER,
+        "easter" => next_holiday(today, 0)?,
+        "eastermonday" => next_holiday(today, 1)?,
+        "goodfriday" => next_holiday(today, -2)?,
+        "ascension" => next_holiday(today, 39)?,
+        "pentecost" => next_holiday(today, 49)?,
+        "midsommar" => next_midsommar(today, Weekday::Sat)?,
+        "midsommarafton" => next_midsommar(today, Weekday::Fri)?,
+        _ => return Ok(None),
+    };
+    Ok(Some(date))
+}
+
+/// Date and time of a Taskwarrior named date: periods end at 23:59:59 and
+/// durations count from now.
+pub(crate) fn datetime(
+    input: &str,
+    now: NaiveDateTime,
+) -> Result<Option<NaiveDateTime>, anyhow::Error> {
+    if let Some(duration) = iso_duration(input) {
+        return duration
+            .add_to_datetime(now)
+            .map(Some)
+            .ok_or_else(|| ParseError::OutOfRange.into());
+    }
+    match Marker::parse(input) {
+        Some(marker) if marker.end => Ok(Some(marker.resolve(now.date())?.and_time(END_OF_DAY))),
+        _ => Ok(None),
+    }
+}
+
+/// Easter Sunday of `year` (anonymous Gregorian algorithm).
+fn easter(year: i32) -> Option<NaiveDate> {
+    let a = year % 19;
+    let b = year / 100;
+    let c = year % 100;
+    let d = b / 4;
+    let e = b % 4;
+    let f = (b + 8) / 25;
+    let g = (b - f + 1) / 3;
+    let h = (19 * a + b - d - g + 15) % 30;
+    let i = c / 4;
+    let k = c % 4;
+    let l = (32 + 2 * e + 2 * i - h - k) % 7;
+    let m = (a + 11 * h + 22 * l) / 451;
+    let month = (h + l - 7 * m + 114) / 31;
+    let day = (h + l - 7 * m + 114) % 31 + 1;
+    NaiveDate::from_ymd_opt(year, month as u32, day as u32)
+}
+
+/// Next day `days` after Easter, today included.
+fn next_holiday(today: NaiveDate, days: i64) -> Result<NaiveDate, anyhow::Error> {
+    for year in [today.year(), today.year() + 1] {
+        let date = easter(year).ok_or(ParseError::OutOfRange)?;
+        let date = add_days(date, days)?;
+        if date >= today {
+            return Ok(date);
+        }
+    }
+    Err(ParseError::OutOfRange.into())
+}
+
+/// Next Swedish midsummer: the Saturday between June 20th and 26th, or the
+/// Friday before it for the eve.
+fn next_midsommar(today: NaiveDate, weekday: Weekday) -> Result<NaiveDate, anyhow::Error> {
+    for year in [today.year(), today.year() + 1] {
+        let first = NaiveDate::from_ymd_opt(year, 6, 20).ok_or(ParseError::OutOfRange)?;
+        let days =
+            (Weekday::Sat.num_days_from_monday() + 7 - first.weekday().num_days_from_monday()) % 7;
+        let saturday = add_days(first, days.into())?;
+        let date = match weekday {
+            Weekday::Sat => saturday,
+            _ => add_days(saturday, -1)?,
+        };
+        if date >= today {
+            return Ok(date);
+        }
+    }
+    Err(ParseError::OutOfRange.into())
+}
+
+/// ISO-8601 duration such as `P1Y2M10DT2H30M` or `P3W`.
+fn iso_duration(input: &str) -> Option<CalendarDuration> {
+    input
+        .starts_with('p')
+        .then(|| duration::parse(input).ok())
+        .flatten()
+}
@@ -0,0 +1,128 @@
+use chrono::NaiveDate;
+use temporis::{Column, Normalizer, ParseError, Parser, Report, RowError};
+
+fn ymd(year: i32, month: u32, day: u32) -> NaiveDate {
+    NaiveDate::from_ymd_opt(year, month, day).unwrap()
+}
+
+fn normalize(normalizer: Normalizer, input: &str) -> (String, Report) {
+    let mut output = Vec::new();
+    let report = normalizer
+        .reference(ymd(2024, 1, 16))
+        .normalize(input.as_bytes(), &mut output)
+        .unwrap();
+    (String::from_utf8(output).unwrap(), report)
+}
+
+#[test]
+fn test_normalize_column() {
+    let input = "id,due,note\n\
+                 1,16/01/2024,\"a, b\"\n\
+                 2,jan-20,c\n\
+                 3,tomorrow,d\n\
+                 4,,e\n\
+                 5,2024-02-01,f\n";
+    let (output, report) = normalize(Normalizer::new(Column::Name("due".into())), input);
+    assert_eq!(
+        output,
+        "id,due,note\n\
+         1,2024-01-16,\"a, b\"\n\
+         2,2024-01-20,c\n\
+         3,2024-01-17,d\n\
+         4,,e\n\
+         5,2024-02-01,f\n"
+    );
+    assert_eq!(report.rows, 5);
+    assert_eq!(report.converted, 4);
+    assert!(report.errors.is_empty());
+}
+
+#[test]
+fn test_normalize_errors() {
+    let input = "due\nxyz\nfeb 30\ntomorow\nxyz\neom\n";
+    let (output, report) = normalize(Normalizer::new(Column::Index(0)), input);
+
+    // Failed values are kept as is
+    assert_eq!(output, "due\nxyz\nfeb 30\ntomorow\nxyz\n2024-01-31\n");
+    assert_eq!(report.converted, 1);
+    assert_eq!(
+        report.errors[..2],
+        [
+            RowError {
+                line: 2,
+                value: "xyz".into(),
+                error: ParseError::Unrecognized {
+                    suggestions: vec![]
+                },
+            },
+            RowError {
+                line: 3,
+                value: "feb 30".into(),
+                error: ParseError::InvalidDate,
+            },
+        ]
+    );
+    assert!(matches!(
+        &report.errors[2].error,
+        ParseError::Unrecognized { suggestions } if suggestions == &["tomorrow"]
+    ));
+
+    // Repeated values are reported on every line
+    assert_eq!(report.errors[3].line, 5);
+
+    // Each row keeps its own reason
+    let input = "due\n1d2d\n99999999999999999999d\n";
+    let (_, report) = normalize(Normalizer::new(Column::Index(0)), input);
+    let errors: Vec<_> = report.errors.into_iter().map(|row| row.error).collect();
+    assert_eq!(
+        errors,
+        [ParseError::RepeatedUnit("day"), ParseError::OutOfRange]
+    );
+}
+
+#[test]
+fn test_normalize_ragged_rows() {
+    let input = "id,due,note\n1,jan-20,a\n2\n3,tomorrow,c,extra\n4,eom,d\n";
+    let (output, report) = normalize(Normalizer::new(Column::Name("due".into())), input);
+
+    // Short rows are reported and copied, long ones converted; the run goes on
+    assert_eq!(
+        output,
+        "id,due,note\n1,2024-01-20,a\n2\n3,2024-01-17,c,extra\n4,2024-01-31,d\n"
+    );
+    assert_eq!(report.rows, 4);
+    assert_eq!(report.converted, 3);
+    assert_eq!(
+        report.errors,
+        [RowError {
+            line: 3,
+            value: String::new(),
+            error: ParseError::MissingField,
+        }]
+    );
+}
+
+#[test]
+fn test_normalize_options() {
+    let (output, _) = normalize(
+        Normalizer::new(Column::Index(1))
+            .delimiter(b'\t')
+            .has_headers(false),
+        "a\t16/01/2024\nb\tjan-20\n",
+    );
+    assert_eq!(output, "a\t2024-01-16\nb\t2024-01-20\n");
+
+    let (output, _) = normalize(
+        Normalizer::new(Column::Index(0)).parser(Parser::new().clamp_days(true)),
+        "due\n31st\n",
+    );
+    assert_eq!(output, "due\n2024-01-31\n");
+
+    let mut output = Vec::new();
+    let missing = Normalizer::new(Column::Name("due".into())).normalize(&b"a,b\n"[..], &mut output);
+    assert!(missing.is_err());
+    let headless = Normalizer::new(Column::Name("due".into()))
+        .has_headers(false)
+        .normalize(&b"a,b\n"[..], &mut output);
+    assert!(headless.is_err());
+}
@@ -0,0 +1,90 @@
+use chrono::{Local, NaiveDate};
+use clap::Parser as _;
+use temporis::{DateRange, NaturalDate, NaturalDateTime, NaturalRange, Parser};
+
+fn ymd(year: i32, month: u32, day: u32) -> NaiveDate {
+    NaiveDate::from_ymd_opt(year, month, day).unwrap()
+}
+
+#[derive(clap::Parser, Debug)]
+struct Args {
+    #[arg(long, value_parser = temporis::clap::date())]
+    due: Option<NaiveDate>,
+    #[arg(long, value_parser = temporis::clap::range())]
+    during: Option<DateRange>,
+    #[arg(long, value_parser = temporis::clap::datetime())]
+    at: Option<chrono::NaiveDateTime>,
+    #[arg(long, value_parser = temporis::clap::date().parser(Parser::new().strict(true)))]
+    strict: Option<NaiveDate>,
+    #[arg(long)]
+    wait: Option<NaturalDate>,
+    #[arg(long)]
+    span: Option<NaturalRange>,
+    #[arg(long)]
+    remind: Option<NaturalDateTime>,
+}
+
+fn error(args: &[&str]) -> String {
+    let args = std::iter::once("tool").chain(args.iter().copied());
+    Args::try_parse_from(args).unwrap_err().to_string()
+}
+
+#[test]
+fn test_value_parsers() {
+    let args = Args::try_parse_from([
+        "tool",
+        "--due",
+        "16 jan 2024",
+        "--during",
+        "mar-2025",
+        "--at",
+        "2024-01-16 at 9am",
+        "--wait",
+        "today",
+        "--span",
+        "2024",
+        "--remind",
+        "16/01/2024 14:30",
+    ])
+    .unwrap();
+    assert_eq!(args.due, Some(ymd(2024, 1, 16)));
+    assert_eq!(
+        args.during,
+        Some(DateRange::new(ymd(2025, 3, 1), ymd(2025, 3, 31)))
+    );
+    assert_eq!(
+        args.at,
+        Some(ymd(2024, 1, 16).and_hms_opt(9, 0, 0).unwrap())
+    );
+    assert_eq!(
+        args.wait,
+        Some(NaturalDate::new("today", Local::now().date_naive()))
+    );
+    assert_eq!(args.span.unwrap().to_string(), "2024-01-01..2024-12-31");
+    let remind = args.remind.unwrap();
+    assert_eq!(remind.expr, "16/01/2024 14:30");
+    assert_eq!(remind.to_string(), "2024-01-16 14:30:00");
+}
+
+#[test]
+fn test_value_parser_errors() {
+    let err = error(&["--due", "tomorow"]);
+    assert!(err.starts_with(
+        "error: invalid value 'tomorow' for '--due <DUE>': Unrecognized date format\n"
+    ));
+    assert!(err.contains("tip: a similar value exists: 'tomorrow'"));
+
+    let err = error(&["--wait", "sowy"]);
+    assert!(err.contains("tip: some similar values exist: 'sow', 'soww', 'soy'"));
+
+    let err = error(&["--during", "feb 30 2024"]);
+    assert!(err
+        .starts_with("error: invalid value 'feb 30 2024' for '--during <DURING>': Invalid date\n"));
+    assert!(!err.contains("tip:"));
+
+    let err = error(&["--at", "tomorrow 25:00"]);
+    assert!(err.contains(": Invalid time"));
+    assert!(error(&["--strict", "16/01/24"]).contains("Unrecognized date format"));
+    assert!(error(&["--span", "xyz"]).contains("Unrecognized date format"));
+    assert!(error(&["--remind", "xyz"]).contains("Unrecognized date format"));
+}
@@ -0,0 +1,199 @@
+use std::io::Write;
+use std::path::Path;
+use std::process::{Command, Output, Stdio};
+
+fn temporis(args: &[&str], stdin: &str) -> Output {
+    temporis_with_config(None, args, stdin)
+}
+
+/// Runs the binary with `TEMPORIS_CONFIG` set to `config`, away from the
+/// user's own config file.
+fn temporis_with_config(config: Option<&Path>, args: &[&str], stdin: &str) -> Output {
+    let mut command = Command::new(env!("CARGO_BIN_EXE_temporis"));
+    command
+        .env("XDG_CONFIG_HOME", env!("CARGO_TARGET_TMPDIR"))
+        .env_remove("TEMPORIS_CONFIG");
+    if let Some(config) = config {
+        command.env("TEMPORIS_CONFIG", config);
+    }
+    let mut child = command
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .unwrap();
+    child
+        .stdin
+        .take()
+        .unwrap()
+        .write_all(stdin.as_bytes())
+        .unwrap();
+    child.wait_with_output().unwrap()
+}
+
+fn stdout(output: &Output) -> &str {
+    std::str::from_utf8(&output.stdout).unwrap()
+}
+
+#[test]
+fn test_cli_arguments() {
+    let output = temporis(&["-r", "2024-01-16", "eom"], "");
+    assert!(output.status.success());
+    assert_eq!(stdout(&output), "2024-01-31\n");
+
+    // Each argument is an expression of its own
+    let output = temporis(&["-r", "2024-01-16", "2nd tue of march"], "");
+    assert_eq!(stdout(&output), "2024-03-12\n");
+    let output = temporis(&["-r", "2024-01-16", "eom", "tomorrow"], "");
+    assert!(output.status.success());
+    assert_eq!(stdout(&output), "2024-01-31\n2024-01-17\n");
+    let output = temporis(&["-r", "2024-01-16", "xyz", "eom", "feb 30"], "");
+    assert_eq!(output.status.code(), Some(3));
+    assert_eq!(stdout(&output), "2024-01-31\n");
+
+    let output = temporis(&["-r", "2024-01-16", "-f", "%d/%m/%Y", "tomorrow"], "");
+    assert_eq!(stdout(&output), "17/01/2024\n");
+
+    // The reference date is itself an expression
+    let output = temporis(&["--reference", "16 jan 2024", "eow"], "");
+    assert_eq!(stdout(&output), "2024-01-21\n");
+}
+
+#[test]
+fn test_cli_stdin() {
+    let output = temporis(&["-r", "2024-01-16"], "today\n\nsom\n");
+    assert!(output.status.success());
+    assert_eq!(stdout(&output), "2024-01-16\n2024-02-01\n");
+
+    // Every line is printed and the first failure decides the exit code
+    let output = temporis(&["-r", "2024-01-16"], "feb 30\nxyz\neom\n");
+    assert_eq!(output.status.code(), Some(4));
+    assert_eq!(stdout(&output), "2024-01-31\n");
+}
+
+#[test]
+fn test_cli_json() {
+    let output = temporis(&["-r", "2024-01-16", "--json"], "eom\ntomorow\n");
+    assert_eq!(output.status.code(), Some(3));
+    let lines: Vec<serde_json::Value> = stdout(&output)
+        .lines()
+        .map(|line| serde_json::from_str(line).unwrap())
+        .collect();
+    assert_eq!(
+        lines[0],
+        serde_json::json!({ "expression": "eom", "date": "2024-01-31" })
+    );
+    assert_eq!(lines[1]["expression"], "tomorow");
+    assert_eq!(
+        lines[1]["error"],
+        "Unrecognized date format, did you mean 'tomorrow'?"
+    );
+}
+
+#[test]
+fn test_cli_explain() {
+    let output = temporis(&["-r", "2024-01-16", "--explain", "eom-2d"], "");
+    assert!(output.status.success());
+    assert_eq!(
+        stdout(&output),
+        "input: eom-2d\n\
+         reference: 2024-01-16\n\
+         KEYWORD: eom = 2024-01-31\n\
+         ARITHMETIC: 2024-01-31 minus 2 days = 2024-01-29\n\
+         result: 2024-01-29\n"
+    );
+    assert_eq!(
+        temporis(&["--explain", "feb 30 2024"], "").status.code(),
+        Some(4)
+    );
+}
+
+#[test]
+fn test_cli_exit_codes() {
+    assert_eq!(temporis(&["xyz"], "").status.code(), Some(3));
+    assert_eq!(temporis(&["feb 30 2024"], "").status.code(), Some(4));
+    assert_eq!(temporis(&["mon 16 jan 2024"], "").status.code(), Some(4));
+
+    // Bad options
+    assert_eq!(temporis(&["-f", "%Q", "today"], "").status.code(), Some(1));
+    assert_eq!(
+        temporis(&["--tz", "Mars/Olympus", "today"], "")
+            .status
+            .code(),
+        Some(1)
+    );
+    assert_eq!(temporis(&["-r", "xyz", "today"], "").status.code(), Some(1));
+    assert_eq!(
+        temporis(&["--locale", "xx", "today"], "").status.code(),
+        Some(2)
+    );
+    assert_eq!(temporis(&["--bogus", "today"], "").status.code(), Some(2));
+}
+
+#[test]
+fn test_cli_tz_and_locale() {
+    for tz in ["utc", "UTC", "+02:00", "-11:30", "Europe/Paris", "local"] {
+        assert!(
+            temporis(&[&format!("--tz={}", tz), "today"], "")
+                .status
+                .success(),
+            "{}",
+            tz
+        );
+    }
+
+    let output = temporis(&["-r", "2024-01-16", "--locale", "fr", "demain"], "");
+    assert_eq!(stdout(&output), "2024-01-17\n");
+    let output = temporis(&["--locale", "de_DE.UTF-8", "16. Januar 2025"], "");
+    assert_eq!(stdout(&output), "2025-01-16\n");
+}
+
+#[test]
+fn test_cli_normalize() {
+    let input = "task,due\nreport,16/01/2024\nreview,tomorrow\nbad,xyz\n";
+    let output = temporis(&["normalize", "-c", "due", "-r", "2024-01-16"], input);
+    assert_eq!(output.status.code(), Some(3));
+    assert_eq!(
+        stdout(&output),
+        "task,due\nreport,2024-01-16\nreview,2024-01-17\nbad,xyz\n"
+    );
+    assert_eq!(
+        std::str::from_utf8(&output.stderr).unwrap(),
+        "temporis: line 4: xyz: Unrecognized date format\n"
+    );
+
+    let output = temporis(
+        &["normalize", "--tsv", "--no-headers", "--column", "2"],
+        "a\t2024-02-01\n",
+    );
+    assert!(output.status.success());
+    assert_eq!(stdout(&output), "a\t2024-02-01\n");
+
+    assert_eq!(
+        temporis(&["normalize", "-c", "due"], "a,b\n").status.code(),
+        Some(1)
+    );
+}
+
+#[test]
+fn test_cli_config() {
+    let config = Path::new(env!("CARGO_TARGET_TMPDIR")).join("cli-config.toml");
+    std::fs::write(
+        &config,
+        "locale = \"fr\"\n[keywords]\nrelease = 2024-03-01\n",
+    )
+    .unwrap();
+    let output = temporis_with_config(Some(&config), &["-r", "2024-01-16"], "demain\nrelease-1d\n");
+    assert_eq!(stdout(&output), "2024-01-17\n2024-02-29\n");
+
+    // Options override the file
+    let output = temporis_with_config(Some(&config), &["--locale", "en", "demain"], "");
+    assert_eq!(output.status.code(), Some(3));
+
+    std::fs::write(&config, "locale = \"fr\"\nanchor = 1\n").unwrap();
+    let output = temporis_with_config(Some(&config), &["today"], "");
+    assert_eq!(output.status.code(), Some(1));
+    let stderr = std::str::from_utf8(&output.stderr).unwrap();
+    assert!(stderr.starts_with(&format!("temporis: {}: line 2: ", config.display())));
+}
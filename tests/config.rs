@@ -0,0 +1,152 @@
+use chrono::NaiveDate;
+use temporis::{Anchor, Locale, ParserConfig};
+
+fn ymd(year: i32, month: u32, day: u32) -> NaiveDate {
+    NaiveDate::from_ymd_opt(year, month, day).unwrap()
+}
+
+fn error(source: &str) -> String {
+    ParserConfig::from_toml(source).unwrap_err().to_string()
+}
+
+#[test]
+fn test_config_options() {
+    let config = ParserConfig::from_toml(
+        r#"
+        # Shared by every tool
+        locale = "de_DE.UTF-8"
+        strict = true
+        autocorrect = true
+        two_digit_year_window = 20
+        anchor = "end"
+        clamp_days = true
+
+        [keywords]
+        release = 2024-03-01
+        freeze = "release - 1w"
+        payday = "last fri of month"
+        "#,
+    )
+    .unwrap();
+    assert_eq!(config.locale, Some(Locale::German));
+    assert_eq!(config.strict, Some(true));
+    assert_eq!(config.anchor, Some(Anchor::End));
+    assert_eq!(
+        config.keywords.names().collect::<Vec<_>>(),
+        ["freeze", "payday", "release"]
+    );
+
+    let parser = config.parser();
+    let today = ymd(2024, 1, 16);
+    let date = |input| parser.parse_relative_to(input, today).unwrap();
+    assert_eq!(date("morgen"), ymd(2024, 1, 17));
+    assert_eq!(date("freeze"), ymd(2024, 2, 23));
+    assert_eq!(date("payday"), ymd(2024, 1, 26));
+    assert_eq!(date("märz"), ymd(2024, 3, 31));
+    assert_eq!(date("31st of april"), ymd(2024, 4, 30));
+    assert_eq!(date("tomorow"), ymd(2024, 1, 17));
+    assert!(parser.parse_relative_to("16/01/24", today).is_err());
+
+    let parser = ParserConfig::from_toml("taskwarrior = true")
+        .unwrap()
+        .parser();
+    assert_eq!(
+        parser.parse_relative_to("sopm", today).unwrap(),
+        ymd(2023, 12, 1)
+    );
+
+    let parser = ParserConfig::from_toml("couple = 3\nfew = 5")
+        .unwrap()
+        .parser();
+    assert_eq!(
+        parser.parse_relative_to("a few days", today).unwrap(),
+        ymd(2024, 1, 21)
+    );
+
+    // Aliases are read with the settings of the file
+    let parser = ParserConfig::from_toml(
+        "taskwarrior = true\nlocale = \"fr\"\n[keywords]\nstart = \"sod\"\nsoon = \"demain\"",
+    )
+    .unwrap()
+    .parser();
+    assert_eq!(parser.parse_relative_to("start", today).unwrap(), today);
+    assert_eq!(
+        parser.parse_relative_to("soon", today).unwrap(),
+        ymd(2024, 1, 17)
+    );
+
+    // An empty file keeps the defaults
+    let parser = ParserConfig::from_toml("").unwrap().parser();
+    assert_eq!(
+        parser.parse_relative_to("march", today).unwrap(),
+        ymd(2024, 3, 1)
+    );
+}
+
+#[test]
+fn test_config_errors() {
+    assert_eq!(
+        error("strict = true\nlocale = \"xx\"\n"),
+        "line 2: Unsupported locale 'xx'"
+    );
+    assert_eq!(
+        error("\n\nanchor = \"middle\""),
+        "line 3: Unknown anchor 'middle', expected start or end"
+    );
+    assert_eq!(
+        error("two_digit_year_window = 150"),
+        "line 1: Window must be at most 99 years"
+    );
+    assert!(error("strict = true\nweekstart = \"monday\"")
+        .starts_with("line 2: unknown field `weekstart`"));
+    assert!(error("strict = \"yes\"").starts_with("line 1: "));
+    assert!(error("strict = ").starts_with("line 1: "));
+
+    let keywords = "[keywords]\nrelease = 2024-03-01\n";
+    assert_eq!(
+        error(&format!("{}eom = 2024-03-01", keywords)),
+        "line 3: 'eom' is a built-in keyword"
+    );
+    // Words of the locale are built in too
+    assert_eq!(
+        error("locale = \"fr\"\n[keywords]\nmars = 2024-03-01"),
+        "line 3: 'mars' is a built-in keyword"
+    );
+    assert_eq!(
+        error("locale = \"fr\"\n[keywords]\ndemain = \"eom\""),
+        "line 3: 'demain' is a built-in keyword"
+    );
+    assert!(ParserConfig::from_toml("[keywords]\nmars = 2024-03-01").is_ok());
+    assert_eq!(
+        error(&format!("{}freeze = \"whenever\"", keywords)),
+        "line 3: Unrecognized expression 'whenever' for 'freeze'"
+    );
+    assert_eq!(
+        error(&format!("{}standup = 09:30:00", keywords)),
+        "line 3: Keyword 'standup' must be a date without a time"
+    );
+    assert_eq!(
+        error(&format!("{}sprint = 14", keywords)),
+        "line 3: Keyword 'sprint' must be a date or an expression"
+    );
+    // Aliases only see the keywords above them
+    assert_eq!(
+        error("[keywords]\nfreeze = \"release - 1w\"\nrelease = 2024-03-01"),
+        "line 2: Unrecognized expression 'release - 1w' for 'freeze'"
+    );
+}
+
+#[test]
+fn test_config_load() {
+    let path = std::path::Path::new(env!("CARGO_TARGET_TMPDIR")).join("config-load.toml");
+    std::fs::write(&path, "locale = \"fr\"\nanchor = \"begin\"\n").unwrap();
+    let err = ParserConfig::load(&path).unwrap_err();
+    assert_eq!(
+        format!("{:#}", err),
+        format!(
+            "{}: line 2: Unknown anchor 'begin', expected start or end",
+            path.display()
+        )
+    );
+    assert!(ParserConfig::load(path.with_extension("missing")).is_err());
+}
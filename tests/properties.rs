@@ -0,0 +1,166 @@
+use chrono::{Datelike, NaiveDate};
+use proptest::prelude::*;
+use temporis::{
+    parse_date, parse_datetime, parse_duration, parse_interval, parse_predicate, parse_range,
+    recognition, Locale, ParseError, Parser,
+};
+
+const SUFFIXES: [&str; 24] = [
+    "d",
+    "days",
+    "w",
+    "weeks",
+    "m",
+    "months",
+    "y",
+    "years",
+    "mon",
+    "friday",
+    "sun",
+    "st",
+    "nd",
+    "rd",
+    "th",
+    "-jan",
+    "/12",
+    "-2024",
+    " jan",
+    " of next month",
+    " of eoq",
+    "fri of month",
+    " day of feb",
+    "",
+];
+
+const WORDS: [&str; 16] = [
+    "today",
+    "tomorrow",
+    "yesterday",
+    "eom",
+    "eonq",
+    "sow",
+    "eoww",
+    "march",
+    "next month",
+    "last day of",
+    "2nd tue of",
+    "tue",
+    "'99",
+    "of",
+    "the",
+    "-",
+];
+
+fn number() -> impl Strategy<Value = String> {
+    prop_oneof![
+        "-?[0-9]{1,3}",
+        "-?[0-9]{10,30}",
+        any::<i64>().prop_map(|n| n.to_string()),
+        any::<u64>().prop_map(|n| n.to_string()),
+    ]
+}
+
+// Concatenations of numbers, units and keywords the grammar knows about
+fn expression() -> impl Strategy<Value = String> {
+    let token = prop_oneof![
+        (number(), prop::sample::select(&SUFFIXES[..])).prop_map(|(n, s)| format!("{}{}", n, s)),
+        prop::sample::select(&WORDS[..]).prop_map(String::from),
+    ];
+    prop::collection::vec(token, 1..5).prop_map(|tokens| tokens.join(" "))
+}
+
+// Any printable text, alone or after the start of a keyword
+fn text() -> impl Strategy<Value = String> {
+    let prefix = prop::sample::select(vec!["", "so", "eo", "sod", "eom", "p", "-", "1"]);
+    (prefix, "\\PC*").prop_map(|(prefix, rest)| format!("{}{}", prefix, rest))
+}
+
+fn reference() -> impl Strategy<Value = NaiveDate> {
+    let first = NaiveDate::MIN.num_days_from_ce();
+    let last = NaiveDate::MAX.num_days_from_ce();
+    prop_oneof![
+        Just(NaiveDate::MIN),
+        Just(NaiveDate::MAX),
+        (first..=last).prop_map(|days| NaiveDate::from_num_days_from_ce_opt(days).unwrap()),
+        (-50i32..50).prop_map(|days| NaiveDate::MAX - chrono::Duration::days(days.abs() as i64)),
+    ]
+}
+
+proptest! {
+    #[test]
+    fn arbitrary_input_never_panics(input in text()) {
+        let _ = parse_date(&input);
+        let _ = parse_range(&input);
+        let _ = parse_datetime(&input);
+        let _ = parse_duration(&input);
+        let _ = parse_interval(&input);
+        let _ = parse_predicate(&input);
+        for locale in [Locale::French, Locale::German, Locale::Spanish] {
+            let _ = Parser::new().locale(locale).parse(&input);
+        }
+        let _ = Parser::new().taskwarrior(true).parse(&input);
+    }
+
+    #[test]
+    fn grammar_shaped_input_never_panics(input in expression(), today in reference()) {
+        let parser = Parser::new();
+        let _ = parser.parse_relative_to(&input, today);
+        let _ = parser.parse_range_relative_to(&input, today);
+        let _ = parser.parse_datetime_relative_to(&format!("{} 12:30pm", input), today.and_time(chrono::NaiveTime::MIN));
+
+        let parser = Parser::new().clamp_days(true).two_digit_year_window(99);
+        let _ = parser.parse_relative_to(&input, today);
+    }
+
+    #[test]
+    fn single_pass_recognition_agrees_with_cascade(input in prop_oneof![expression(), text()]) {
+        let input = input.to_lowercase();
+        prop_assert_eq!(recognition::single_pass(&input), recognition::cascade(&input));
+    }
+
+    #[test]
+    fn errors_are_parse_errors(input in prop_oneof![expression(), text()], today in reference()) {
+        let parser = Parser::new();
+        let errors = [
+            parser.parse_relative_to(&input, today).err(),
+            parser.parse_range_relative_to(&input, today).err(),
+        ];
+        for err in errors.into_iter().flatten() {
+            prop_assert!(err.downcast_ref::<ParseError>().is_some(), "{:?}: {}", input, err);
+        }
+    }
+
+    #[test]
+    fn markers_with_any_ending_never_panic(
+        prefix in prop::sample::select(vec!["so", "eo", "sod", "eom", "son", "so2n", "someday"]),
+        rest in "\\PC{0,3}",
+    ) {
+        let input = format!("{}{}", prefix, rest);
+        let _ = parse_date(&input);
+        let _ = Parser::new().taskwarrior(true).parse(&input);
+    }
+
+    #[test]
+    fn huge_amounts_are_out_of_range(
+        digits in "[1-9][0-9]{19,40}",
+        unit in prop::sample::select(vec!["d", "w", "m", "y", "mon", "friday"]),
+    ) {
+        let err = parse_date(&format!("{}{}", digits, unit)).unwrap_err();
+        prop_assert_eq!(err.downcast_ref::<ParseError>(), Some(&ParseError::OutOfRange));
+    }
+
+    #[test]
+    fn day_offsets_match_checked_arithmetic(amount in any::<i64>(), today in reference()) {
+        let expected = chrono::Duration::try_days(amount)
+            .and_then(|duration| today.checked_add_signed(duration));
+        let result = Parser::new().parse_relative_to(&format!("{}d", amount), today);
+        match (result, expected) {
+            (Ok(date), Some(expected)) => prop_assert_eq!(date, expected),
+            (Err(err), None) => prop_assert_eq!(
+                err.downcast_ref::<ParseError>(),
+                Some(&ParseError::OutOfRange)
+            ),
+            (result, expected) => prop_assert!(false, "{:?} != {:?}", result, expected),
+        }
+    }
+}
@@ -0,0 +1,63 @@
+use chrono::{Local, NaiveDate};
+use serde::{Deserialize, Serialize};
+use temporis::NaturalDate;
+
+fn ymd(year: i32, month: u32, day: u32) -> NaiveDate {
+    NaiveDate::from_ymd_opt(year, month, day).unwrap()
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq)]
+struct Task {
+    #[serde(with = "temporis::serde::natural")]
+    due: NaiveDate,
+    #[serde(with = "temporis::serde::natural_option", default)]
+    wait: Option<NaiveDate>,
+}
+
+#[test]
+fn test_natural_fields() {
+    let task: Task =
+        serde_json::from_str(r#"{ "due": "2024-01-16", "wait": "16 jan 2024" }"#).unwrap();
+    assert_eq!(task.due, ymd(2024, 1, 16));
+    assert_eq!(task.wait, Some(ymd(2024, 1, 16)));
+
+    let task: Task = serde_json::from_str(r#"{ "due": "tomorrow", "wait": null }"#).unwrap();
+    assert_eq!(task.due, Local::now().date_naive().succ_opt().unwrap());
+    assert_eq!(task.wait, None);
+
+    let task: Task = serde_json::from_str(r#"{ "due": "Jan 16, 2024" }"#).unwrap();
+    assert_eq!(task.wait, None);
+
+    // Dates are written back as ISO dates
+    assert_eq!(
+        serde_json::to_string(&task).unwrap(),
+        r#"{"due":"2024-01-16","wait":null}"#
+    );
+
+    let err = serde_json::from_str::<Task>(r#"{ "due": "tomorow" }"#).unwrap_err();
+    assert!(err
+        .to_string()
+        .starts_with("'tomorow': Unrecognized date format, did you mean 'tomorrow'?"));
+    assert!(serde_json::from_str::<Task>(r#"{ "due": "feb 30 2024" }"#).is_err());
+    assert!(serde_json::from_str::<Task>(r#"{ "due": 20240116 }"#).is_err());
+}
+
+#[test]
+fn test_natural_date() {
+    let date = NaturalDate::new("eom", ymd(2024, 1, 31));
+    let json = serde_json::to_string(&date).unwrap();
+    assert_eq!(json, r#"{"expr":"eom","date":"2024-01-31"}"#);
+
+    // The stored date is kept rather than resolved again
+    assert_eq!(serde_json::from_str::<NaturalDate>(&json).unwrap(), date);
+
+    let parsed: NaturalDate = serde_json::from_str(r#""16 jan 2024""#).unwrap();
+    assert_eq!(parsed, NaturalDate::new("16 jan 2024", ymd(2024, 1, 16)));
+    let parsed: NaturalDate = serde_json::from_str(r#"{ "expr": "2024-03-01" }"#).unwrap();
+    assert_eq!(parsed.date, ymd(2024, 3, 1));
+
+    assert!(serde_json::from_str::<NaturalDate>(r#""xyz""#).is_err());
+    assert!(serde_json::from_str::<NaturalDate>(r#"{ "date": "2024-01-31" }"#).is_err());
+    assert!(serde_json::from_str::<NaturalDate>(r#"{ "expr": "eom", "date": "eom" }"#).is_err());
+    assert!(serde_json::from_str::<NaturalDate>(r#"{ "expr": "eom", "when": 1 }"#).is_err());
+}
@@ -1,5 +1,10 @@
-use chrono::{Datelike, Duration, Local, Weekday};
-use temporis::parse_date;
+use chrono::{Datelike, Duration, Local, NaiveDate, Weekday};
+use temporis::{
+    completion_script, explain, extract_dates, parse_date, parse_datetime, parse_duration,
+    parse_interval, parse_predicate, parse_range, parse_resolved, strip_dates, Anchor,
+    CalendarDuration, DatePredicate, DateRange, Interval, KeywordRegistry, Locale, NaturalDate,
+    NaturalDateTime, NaturalRange, ParseContext, ParseError, Parser, Resolved, Shell,
+};
 
 #[test]
 fn test_yyyy_mm_dd_format() {
@@ -271,7 +276,6 @@ fn test_basic_markers() {
 
 #[test]
 fn test_marker_relationships() {
-    let now = Local::now();
     let sow = parse_date("sow").unwrap();
     let eow = parse_date("eow").unwrap();
     let eonw = parse_date("eonw").unwrap();
@@ -351,7 +355,6 @@ fn test_invalid_markers() {
     // Invalid but similar markers
     assert!(parse_date("sowy").is_err());
     assert!(parse_date("eowy").is_err());
-    assert!(parse_date("sonw").is_err());
     assert!(parse_date("sonn").is_err());
 
     // With spaces
@@ -644,7 +647,6 @@ fn test_invalid_relative_formats() {
 
 #[test]
 fn test_case_relative_sensitivity() {
-    let today = Local::now().date_naive();
     let base = parse_date("5d").unwrap();
 
     // Test different casings
@@ -687,8 +689,6 @@ fn test_day_month_formats() {
 
 #[test]
 fn test_month_day_formats() {
-    let today = Local::now().date_naive();
-
     // Test with different separators
     assert!(parse_date("jan-16").is_ok());
     assert!(parse_date("jan/16").is_ok());
@@ -774,9 +774,9 @@ fn test_invalid_date_formats() {
     assert!(parse_date("16-0").is_err());
 
     // Invalid separators
-    assert!(parse_date("16.jan").is_err());
     assert!(parse_date("16_jan").is_err());
-    assert!(parse_date("16 jan").is_err());
+    assert!(parse_date("16--jan").is_err());
+    assert!(parse_date("16,jan").is_err());
 
     // Month-specific day validation
     assert!(parse_date("31-apr").is_err()); // April has 30 days
@@ -989,9 +989,9 @@ fn test_empty_and_malformed() {
         "garbage",  // More random text
         "date",     // Date-related but invalid
         "calendar", // Calendar-related but invalid
-        "2024",     // Just year
-        "january",  // Just month
         "15",       // Just day
+        "'5",       // Partial year
+        "20245",    // Too many digits for a year
     ];
 
     for input in inputs.iter() {
@@ -1137,3 +1137,1666 @@ fn test_invalid_invalid_weekday_formats() {
         );
     }
 }
+
+#[test]
+fn test_directional_markers() {
+    let today = NaiveDate::from_ymd_opt(2024, 2, 14).unwrap();
+    let date = |input| Parser::new().parse_relative_to(input, today).unwrap();
+    let ymd = |year, month, day| NaiveDate::from_ymd_opt(year, month, day).unwrap();
+
+    assert_eq!(date("sopw"), ymd(2024, 2, 5));
+    assert_eq!(date("eopw"), ymd(2024, 2, 11));
+    assert_eq!(date("socw"), ymd(2024, 2, 12));
+    assert_eq!(date("sonw"), date("sow"));
+    assert_eq!(date("sopm"), ymd(2024, 1, 1));
+    assert_eq!(date("eopm"), ymd(2024, 1, 31));
+    assert_eq!(date("socm"), ymd(2024, 2, 1));
+    assert_eq!(date("eocm"), date("eom"));
+    assert_eq!(date("sonm"), date("som"));
+    assert_eq!(date("sopq"), ymd(2023, 10, 1));
+    assert_eq!(date("eopq"), ymd(2023, 12, 31));
+    assert_eq!(date("socq"), ymd(2024, 1, 1));
+    assert_eq!(date("sopy"), ymd(2023, 1, 1));
+    assert_eq!(date("eopy"), ymd(2023, 12, 31));
+    assert_eq!(date("socy"), ymd(2024, 1, 1));
+    assert_eq!(date("sony"), date("soy"));
+
+    // Several periods away
+    assert_eq!(date("so2nm"), ymd(2024, 4, 1));
+    assert_eq!(date("eo2nm"), ymd(2024, 4, 30));
+    assert_eq!(date("so12nm"), ymd(2025, 2, 1));
+    assert_eq!(date("eo3pq"), ymd(2023, 6, 30));
+    assert_eq!(date("so2nw"), ymd(2024, 2, 26));
+    assert_eq!(date("eo10py"), ymd(2014, 12, 31));
+    assert_eq!(date("15th of so2nm"), ymd(2024, 4, 15));
+    assert_eq!(date("sopm+1w"), ymd(2024, 1, 8));
+    assert!(parse_date("so99999999999nm").is_err());
+    assert!(parse_date("eo999999999ny").is_err());
+}
+
+#[test]
+fn test_invalid_directional_markers() {
+    assert!(parse_date("so0nm").is_err());
+    assert!(parse_date("so-2nm").is_err());
+    assert!(parse_date("sod").is_err());
+
+    // With characters wider than a byte
+    assert!(parse_date("soé").is_err());
+    assert!(parse_date("eoß").is_err());
+    assert!(parse_date("eom٣").is_err());
+    assert!(parse_date("someday٣").is_err());
+    assert!(Parser::new().taskwarrior(true).parse("so2né").is_err());
+}
+
+#[test]
+fn test_written_out_dates() {
+    let expected = parse_date("2024-01-16").unwrap();
+
+    // Whitespace, comma and dot separators
+    let inputs = [
+        "January 16, 2024",
+        "january 16 2024",
+        "Jan. 16, 2024",
+        "16 Jan 2024",
+        "16 January, 2024",
+        "16. Jan. 2024",
+        "16.jan.2024",
+        "2024 jan 16",
+        "jan-16-2024",
+        "  16   jan   2024  ",
+    ];
+    for input in inputs.iter() {
+        assert_eq!(parse_date(input).unwrap(), expected, "{}", input);
+    }
+
+    // Without a year the next occurrence is used, like 16-jan
+    let base = parse_date("16-jan").unwrap();
+    assert_eq!(parse_date("16 jan").unwrap(), base);
+    assert_eq!(parse_date("16.jan").unwrap(), base);
+    assert_eq!(parse_date("Jan 16").unwrap(), base);
+    assert_eq!(parse_date("jan. 16").unwrap(), base);
+}
+
+#[test]
+fn test_written_out_ordinals() {
+    let expected = parse_date("2024-01-16").unwrap();
+    assert_eq!(parse_date("16th of January 2024").unwrap(), expected);
+    assert_eq!(parse_date("16th January, 2024").unwrap(), expected);
+    assert_eq!(parse_date("January 16th, 2024").unwrap(), expected);
+    assert_eq!(parse_date("2024-jan-16th").unwrap(), expected);
+
+    let base = parse_date("1-mar").unwrap();
+    assert_eq!(parse_date("1st of march").unwrap(), base);
+    assert_eq!(parse_date("march 1st").unwrap(), base);
+
+    // Suffixes are only allowed on the day
+    assert!(parse_date("16th of jan 2024th").is_err());
+    assert!(parse_date("jan 16th of").is_err());
+    assert!(parse_date("of jan 16").is_err());
+}
+
+#[test]
+fn test_leading_weekday() {
+    let expected = parse_date("2024-01-16").unwrap();
+    let inputs = [
+        "Tue 16 Jan 2024",
+        "Tue, 16 Jan 2024",
+        "Tuesday, January 16, 2024",
+        "tuesday 16th of january 2024",
+        "tue 2024-01-16",
+        "tue 16/01/2024",
+    ];
+    for input in inputs.iter() {
+        assert_eq!(parse_date(input).unwrap(), expected, "{}", input);
+    }
+
+    // Without a year the weekday must match the resolved date
+    let date = parse_date("16 jan").unwrap();
+    let weekday = format!("{}", date.weekday()).to_lowercase();
+    assert_eq!(parse_date(&format!("{} 16 jan", weekday)).unwrap(), date);
+}
+
+#[test]
+fn test_inconsistent_leading_weekday() {
+    let err = parse_date("Mon 16 Jan 2024").unwrap_err();
+    let cause = err.downcast_ref::<ParseError>().unwrap();
+    assert_eq!(
+        cause,
+        &ParseError::WeekdayMismatch {
+            date: parse_date("2024-01-16").unwrap(),
+            weekday: Weekday::Mon,
+        }
+    );
+    assert_eq!(err.to_string(), "2024-01-16 is a Tuesday, not a Monday");
+
+    // Leading weekdays only apply to explicit dates
+    assert!(parse_date("mon tomorrow").is_err());
+    assert!(parse_date("mon 5d").is_err());
+    assert!(parse_date("mon,").is_err());
+}
+
+fn ymd(year: i32, month: u32, day: u32) -> NaiveDate {
+    NaiveDate::from_ymd_opt(year, month, day).unwrap()
+}
+
+#[test]
+fn test_two_digit_years() {
+    let parser = Parser::new();
+    let today = ymd(2026, 10, 18);
+
+    let inputs = [
+        "16/01/24",
+        "16-1-24",
+        "16/01/'24",
+        "jan-16-24",
+        "16 jan '24",
+        "January 16, '24",
+        "16-jan-24",
+    ];
+    for input in inputs.iter() {
+        assert_eq!(
+            parser.parse_relative_to(input, today).unwrap(),
+            ymd(2024, 1, 16),
+            "{}",
+            input
+        );
+    }
+
+    // A leading two-digit field is always the day
+    assert_eq!(
+        parser.parse_relative_to("24-01-16", today).unwrap(),
+        ymd(2016, 1, 24)
+    );
+    assert_eq!(
+        parser.parse_relative_to("24-jan-16", today).unwrap(),
+        ymd(2016, 1, 24)
+    );
+    assert!(parser.parse_relative_to("16/01/124", today).is_err());
+    assert!(parser.parse_relative_to("16/01/''24", today).is_err());
+}
+
+#[test]
+fn test_two_digit_year_window() {
+    let today = ymd(2026, 10, 18);
+
+    // Default window: up to 50 years ahead, otherwise the previous century
+    let parser = Parser::new();
+    let year = |input: &str| parser.parse_relative_to(input, today).unwrap().year();
+    assert_eq!(year("01/01/00"), 2000);
+    assert_eq!(year("01/01/76"), 2076);
+    assert_eq!(year("01/01/77"), 1977);
+    assert_eq!(year("01/01/99"), 1999);
+
+    // The window slides with the reference date
+    let later = ymd(2090, 1, 1);
+    assert_eq!(
+        parser.parse_relative_to("01/01/05", later).unwrap().year(),
+        2105
+    );
+    assert_eq!(
+        parser.parse_relative_to("01/01/41", later).unwrap().year(),
+        2041
+    );
+
+    // Narrower window
+    let parser = Parser::new().two_digit_year_window(10);
+    assert_eq!(
+        parser.parse_relative_to("01/01/36", today).unwrap().year(),
+        2036
+    );
+    assert_eq!(
+        parser.parse_relative_to("01/01/37", today).unwrap().year(),
+        1937
+    );
+}
+
+#[test]
+fn test_strict_rejects_two_digit_years() {
+    let parser = Parser::new().strict(true);
+    let today = ymd(2026, 10, 18);
+
+    assert!(parser.parse_relative_to("16/01/24", today).is_err());
+    assert!(parser.parse_relative_to("jan-16-25", today).is_err());
+    assert!(parser.parse_relative_to("16 jan '25", today).is_err());
+    assert_eq!(
+        parser.parse_relative_to("16/01/2024", today).unwrap(),
+        ymd(2024, 1, 16)
+    );
+}
+
+#[test]
+fn test_month_only() {
+    let parser = Parser::new();
+    let today = ymd(2026, 10, 18);
+
+    // The current month stays this year, past months move to next year
+    assert_eq!(
+        parser.parse_relative_to("october", today).unwrap(),
+        ymd(2026, 10, 1)
+    );
+    assert_eq!(
+        parser.parse_relative_to("Dec", today).unwrap(),
+        ymd(2026, 12, 1)
+    );
+    assert_eq!(
+        parser.parse_relative_to("march", today).unwrap(),
+        ymd(2027, 3, 1)
+    );
+    assert_eq!(
+        parser.parse_relative_to("may", today).unwrap(),
+        ymd(2027, 5, 1)
+    );
+
+    let parser = Parser::new().anchor(Anchor::End);
+    assert_eq!(
+        parser.parse_relative_to("feb", today).unwrap(),
+        ymd(2027, 2, 28)
+    );
+    assert_eq!(
+        parser.parse_relative_to("oct", today).unwrap(),
+        ymd(2026, 10, 31)
+    );
+
+    // Month names still need to be exact
+    assert!(parser.parse_relative_to("marc", today).is_err());
+    assert!(parser.parse_relative_to("sept", today).is_err());
+}
+
+#[test]
+fn test_year_only() {
+    let parser = Parser::new();
+    let today = ymd(2026, 10, 18);
+    assert_eq!(
+        parser.parse_relative_to("2025", today).unwrap(),
+        ymd(2025, 1, 1)
+    );
+    assert_eq!(
+        parser.parse_relative_to("'25", today).unwrap(),
+        ymd(2025, 1, 1)
+    );
+
+    let parser = Parser::new().anchor(Anchor::End);
+    assert_eq!(
+        parser.parse_relative_to("2025", today).unwrap(),
+        ymd(2025, 12, 31)
+    );
+
+    let parser = Parser::new().strict(true);
+    assert!(parser.parse_relative_to("'25", today).is_err());
+}
+
+#[test]
+fn test_month_and_year() {
+    let parser = Parser::new();
+    let today = ymd(2026, 10, 18);
+    let inputs = [
+        "mar-2025",
+        "March 2025",
+        "mar/2025",
+        "mar '25",
+        "2025-03",
+        "2025/3",
+        "2025 mar",
+        "03/2025",
+    ];
+    for input in inputs.iter() {
+        assert_eq!(
+            parser.parse_relative_to(input, today).unwrap(),
+            ymd(2025, 3, 1),
+            "{}",
+            input
+        );
+    }
+
+    let parser = Parser::new().anchor(Anchor::End);
+    assert_eq!(
+        parser.parse_relative_to("feb-2024", today).unwrap(),
+        ymd(2024, 2, 29)
+    );
+
+    // Two-digit fields after a month are still days
+    assert_eq!(
+        parser.parse_relative_to("mar-25", today).unwrap(),
+        ymd(2027, 3, 25)
+    );
+
+    assert!(parser.parse_relative_to("2025-13", today).is_err());
+    assert!(parser.parse_relative_to("13/2025", today).is_err());
+    assert!(parser.parse_relative_to("marc-2025", today).is_err());
+}
+
+#[test]
+fn test_ranges() {
+    let parser = Parser::new();
+    let today = ymd(2026, 10, 18);
+    let range = |input: &str| parser.parse_range_relative_to(input, today).unwrap();
+
+    assert_eq!(
+        range("march"),
+        DateRange::new(ymd(2027, 3, 1), ymd(2027, 3, 31))
+    );
+    assert_eq!(
+        range("2024"),
+        DateRange::new(ymd(2024, 1, 1), ymd(2024, 12, 31))
+    );
+    assert_eq!(
+        range("feb 2024"),
+        DateRange::new(ymd(2024, 2, 1), ymd(2024, 2, 29))
+    );
+    assert_eq!(
+        range("2025-04"),
+        DateRange::new(ymd(2025, 4, 1), ymd(2025, 4, 30))
+    );
+
+    // Anything else covers a single day
+    assert_eq!(range("tomorrow"), DateRange::day(ymd(2026, 10, 19)));
+    assert_eq!(range("16 jan 2024"), DateRange::day(ymd(2024, 1, 16)));
+
+    assert!(range("2024").contains(ymd(2024, 6, 30)));
+    assert!(!range("2024").contains(ymd(2025, 1, 1)));
+    assert!(parse_range("garbage").is_err());
+    assert!(parse_range("january").is_ok());
+}
+
+#[test]
+fn test_nth_weekday_of_month() {
+    let parser = Parser::new();
+    let today = ymd(2026, 10, 18);
+    let parse = |input: &str| parser.parse_relative_to(input, today).unwrap();
+
+    // Named months follow the same year rule as `march`
+    assert_eq!(parse("2nd tue of march"), ymd(2027, 3, 9));
+    assert_eq!(parse("second tuesday in march 2025"), ymd(2025, 3, 11));
+    assert_eq!(parse("1mon-jun"), ymd(2027, 6, 7));
+    assert_eq!(parse("3fri/jan-2025"), ymd(2025, 1, 17));
+    assert_eq!(parse("first monday of oct"), ymd(2026, 10, 5));
+
+    // Current, next and previous month
+    assert_eq!(parse("last fri of month"), ymd(2026, 10, 30));
+    assert_eq!(parse("last friday of the month"), ymd(2026, 10, 30));
+    assert_eq!(parse("1st wed of this month"), ymd(2026, 10, 7));
+    assert_eq!(parse("first friday of next month"), ymd(2026, 11, 6));
+    assert_eq!(parse("4th thu in next month"), ymd(2026, 11, 26));
+    assert_eq!(parse("last sun of last month"), ymd(2026, 9, 27));
+
+    // Next month wraps into the next year
+    let december = ymd(2026, 12, 10);
+    assert_eq!(
+        parser
+            .parse_relative_to("1st mon of next month", december)
+            .unwrap(),
+        ymd(2027, 1, 4)
+    );
+}
+
+#[test]
+fn test_missing_nth_weekday() {
+    let parser = Parser::new();
+    let today = ymd(2026, 10, 18);
+
+    assert_eq!(
+        parser
+            .parse_relative_to("5th fri of october", today)
+            .unwrap(),
+        ymd(2026, 10, 30)
+    );
+    let err = parser
+        .parse_relative_to("5th tue of feb 2025", today)
+        .unwrap_err();
+    assert_eq!(
+        err.downcast_ref::<ParseError>(),
+        Some(&ParseError::NoSuchWeekday {
+            nth: 5,
+            weekday: Weekday::Tue,
+            year: 2025,
+            month: 2,
+        })
+    );
+    assert_eq!(err.to_string(), "February 2025 has no fifth Tuesday");
+
+    // Only months can be used as the period
+    assert!(parser.parse_relative_to("6th tue of march", today).is_err());
+    assert!(parser.parse_relative_to("2nd tue of 2025", today).is_err());
+    assert!(parser.parse_relative_to("2nd tue of week", today).is_err());
+    assert!(parser.parse_relative_to("2nd tue of", today).is_err());
+}
+
+#[test]
+fn test_day_of_period() {
+    let parser = Parser::new();
+    let today = ymd(2026, 10, 18);
+    let parse = |input: &str| parser.parse_relative_to(input, today).unwrap();
+
+    assert_eq!(parse("15th of next month"), ymd(2026, 11, 15));
+    assert_eq!(parse("15th next month"), ymd(2026, 11, 15));
+    assert_eq!(parse("1st of the month"), ymd(2026, 10, 1));
+    assert_eq!(parse("first day of next month"), ymd(2026, 11, 1));
+    assert_eq!(parse("first day of march"), ymd(2027, 3, 1));
+    assert_eq!(parse("last day of feb"), ymd(2027, 2, 28));
+    assert_eq!(parse("last day of february 2024"), ymd(2024, 2, 29));
+    assert_eq!(parse("penultimate day of month"), ymd(2026, 10, 30));
+    assert_eq!(parse("-1st of next month"), ymd(2026, 11, 30));
+    assert_eq!(parse("-3rd of last month"), ymd(2026, 9, 28));
+
+    // Any expression can name the month
+    assert_eq!(parse("3rd of eoq"), ymd(2026, 12, 3));
+    assert_eq!(parse("10th of 2025-01-20"), ymd(2025, 1, 10));
+    assert_eq!(parse("last day of tomorrow"), ymd(2026, 10, 31));
+
+    // A year has a first, last and penultimate day, never a numbered one
+    assert_eq!(parse("last day of 2025"), ymd(2025, 12, 31));
+    assert_eq!(parse("first day of 2025"), ymd(2025, 1, 1));
+    assert_eq!(parse("penultimate day of 2025"), ymd(2025, 12, 30));
+    assert!(parser.parse_relative_to("15th of 2025", today).is_err());
+    assert_eq!(parse("-1st of 2025"), ymd(2025, 12, 31));
+
+    // A bare month name keeps the next occurrence of that day
+    assert_eq!(
+        parse("15th of jan"),
+        parser.parse_relative_to("15-jan", today).unwrap()
+    );
+}
+
+#[test]
+fn test_days_from_end_of_month() {
+    let parser = Parser::new();
+    let today = ymd(2026, 10, 18);
+    assert_eq!(
+        parser.parse_relative_to("-1st", today).unwrap(),
+        ymd(2026, 10, 31)
+    );
+    assert_eq!(
+        parser.parse_relative_to("-2nd", today).unwrap(),
+        ymd(2026, 10, 30)
+    );
+    assert_eq!(
+        parser.parse_relative_to("-14th", today).unwrap(),
+        ymd(2026, 11, 17)
+    );
+
+    // Today is never the next occurrence
+    let last_day = ymd(2026, 10, 31);
+    assert_eq!(
+        parser.parse_relative_to("-1st", last_day).unwrap(),
+        ymd(2026, 11, 30)
+    );
+
+    assert!(parser.parse_relative_to("-0th", today).is_err());
+    assert!(parser.parse_relative_to("-32nd", today).is_err());
+    assert!(parser.parse_relative_to("--1st", today).is_err());
+}
+
+#[test]
+fn test_clamp_days() {
+    let today = ymd(2026, 4, 10);
+
+    // By default months without the day are skipped
+    let parser = Parser::new();
+    assert_eq!(
+        parser.parse_relative_to("31st", today).unwrap(),
+        ymd(2026, 5, 31)
+    );
+    assert!(parser.parse_relative_to("31st of april", today).is_err());
+    assert!(parser.parse_relative_to("30th of feb", today).is_err());
+
+    let parser = Parser::new().clamp_days(true);
+    assert_eq!(
+        parser.parse_relative_to("31st", today).unwrap(),
+        ymd(2026, 4, 30)
+    );
+    assert_eq!(
+        parser.parse_relative_to("31st of april", today).unwrap(),
+        ymd(2026, 4, 30)
+    );
+    assert_eq!(
+        parser.parse_relative_to("30th of feb 2024", today).unwrap(),
+        ymd(2024, 2, 29)
+    );
+    assert_eq!(
+        parser.parse_relative_to("-31st of feb", today).unwrap(),
+        ymd(2027, 2, 1)
+    );
+
+    // Once the clamped day is past, the next month is used
+    let last_day = ymd(2026, 4, 30);
+    assert_eq!(
+        parser.parse_relative_to("31st", last_day).unwrap(),
+        ymd(2026, 5, 31)
+    );
+    assert!(parser.parse_relative_to("0th", today).is_err());
+}
+
+#[test]
+fn test_out_of_range() {
+    let inputs = [
+        "99999999999999999d",
+        "99999999999999999999d",
+        "-99999999999999999w",
+        "9999999999999monday",
+        "99999999999999999999fri",
+        "999999999y",
+        "-999999999m",
+    ];
+    for input in inputs.iter() {
+        let err = parse_date(input).unwrap_err();
+        assert_eq!(
+            err.downcast_ref::<ParseError>(),
+            Some(&ParseError::OutOfRange),
+            "{}",
+            input
+        );
+    }
+
+    // Reference dates at the edge of the calendar
+    let parser = Parser::new();
+    let out_of_range = |input: &str, today: NaiveDate| {
+        let err = parser.parse_relative_to(input, today).unwrap_err();
+        err.downcast_ref::<ParseError>() == Some(&ParseError::OutOfRange)
+    };
+    assert!(out_of_range("tomorrow", NaiveDate::MAX));
+    assert!(out_of_range("yesterday", NaiveDate::MIN));
+    assert!(out_of_range("eoy", NaiveDate::MAX));
+    assert!(out_of_range("som", NaiveDate::MAX));
+    assert!(out_of_range("nmon", NaiveDate::MAX));
+    assert_eq!(
+        parser.parse_relative_to("today", NaiveDate::MAX).unwrap(),
+        NaiveDate::MAX
+    );
+
+    // Overlong input is rejected up front
+    assert!(parse_date(&"1st of ".repeat(1000)).is_err());
+}
+
+fn suggestions(input: &str) -> Vec<String> {
+    match parse_date(input).unwrap_err().downcast_ref::<ParseError>() {
+        Some(ParseError::Unrecognized { suggestions }) => suggestions.clone(),
+        other => panic!("Unexpected error for {}: {:?}", input, other),
+    }
+}
+
+#[test]
+fn test_typo_suggestions() {
+    assert_eq!(suggestions("wednes"), ["wednesday"]);
+    assert_eq!(suggestions("tomorow"), ["tomorrow"]);
+    assert_eq!(suggestions("tommorrow"), ["tomorrow"]);
+    assert_eq!(suggestions("sowy"), ["sow", "soww", "soy", "sopy", "socy"]);
+    assert_eq!(suggestions("16-janury"), ["16-january"]);
+    assert_eq!(suggestions("5dya"), ["5day"]);
+    assert_eq!(
+        suggestions("2nd tuseday of march"),
+        ["2nd tuesday of march"]
+    );
+
+    // Closest suggestions come first
+    assert_eq!(suggestions("nfridy")[0], "nfriday");
+
+    // Nothing close enough, or nothing misspelled
+    assert!(suggestions("xyz").is_empty());
+    assert!(suggestions("garbage").is_empty());
+    assert!(suggestions("monday.").is_empty());
+
+    // Words longer than any keyword, and oversized input, are not corrected
+    assert!(suggestions("tuesdaywednesday").is_empty());
+    assert!(suggestions(&"x".repeat(50_000)).is_empty());
+    assert!(suggestions(&format!("{} tomorow", "1".repeat(300))).is_empty());
+
+    let err = parse_date("tomorow").unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "Unrecognized date format, did you mean 'tomorrow'?"
+    );
+    let err = parse_date("sowy").unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "Unrecognized date format, did you mean 'sow', 'soww', 'soy', 'sopy', or 'socy'?"
+    );
+}
+
+#[test]
+fn test_autocorrect() {
+    let parser = Parser::new().autocorrect(true);
+    let today = ymd(2026, 10, 18);
+    let parse = |input: &str| parser.parse_relative_to(input, today);
+
+    assert_eq!(parse("tomorow").unwrap(), ymd(2026, 10, 19));
+    assert_eq!(parse("janury 16, 2024").unwrap(), ymd(2024, 1, 16));
+    assert_eq!(parse("fridya").unwrap(), ymd(2026, 10, 23));
+
+    // Only unambiguous single-edit typos are fixed
+    assert!(parse("sowy").is_err());
+    assert!(parse("wednes").is_err());
+
+    // Disabled by default
+    assert!(Parser::new().parse_relative_to("tomorow", today).is_err());
+}
+
+#[test]
+fn test_completion() {
+    let parser = Parser::new();
+    let today = ymd(2026, 10, 18);
+    let complete = |prefix: &str| -> Vec<(String, NaiveDate)> {
+        parser
+            .complete_relative_to(prefix, today)
+            .into_iter()
+            .map(|completion| (completion.text, completion.date))
+            .collect()
+    };
+    let texts = |prefix: &str| -> Vec<String> {
+        complete(prefix).into_iter().map(|(text, _)| text).collect()
+    };
+
+    assert_eq!(
+        texts("eo"),
+        [
+            "eow", "eoww", "eom", "eoq", "eoy", "eonw", "eonm", "eonq", "eony", "eopw", "eopm",
+            "eopq", "eopy", "eocw", "eocm", "eocq", "eocy"
+        ]
+    );
+    assert_eq!(complete("eom"), [("eom".to_string(), ymd(2026, 10, 31))]);
+    assert_eq!(texts("Tom"), ["tom", "tomorrow"]);
+    assert_eq!(
+        texts("las"),
+        [
+            "last day of month",
+            "last day of next month",
+            "last friday of month"
+        ]
+    );
+
+    // Every phrase parses, since completion only offers what does
+    let phrases: Vec<_> = texts("")
+        .into_iter()
+        .filter(|text| text.contains(' '))
+        .collect();
+    assert_eq!(
+        phrases,
+        [
+            "first day of next month",
+            "last day of month",
+            "last day of next month",
+            "penultimate day of month",
+            "last friday of month",
+            "first monday of next month"
+        ]
+    );
+
+    // Numbers complete to units, weekdays and their ordinal
+    let three = complete("3");
+    assert!(three.contains(&("3d".to_string(), ymd(2026, 10, 21))));
+    assert!(three.contains(&("3fri".to_string(), ymd(2026, 11, 6))));
+    assert!(three.contains(&("3rd".to_string(), ymd(2026, 11, 3))));
+    assert!(texts("11").contains(&"11th".to_string()));
+    assert!(texts("-2").contains(&"-2nd".to_string()));
+    assert_eq!(texts("3f"), ["3fri"]);
+
+    // Complete input is offered as is
+    assert_eq!(complete("2024")[0], ("2024".to_string(), ymd(2024, 1, 1)));
+    assert_eq!(texts("3days"), ["3days"]);
+
+    // Every completion parses to the date it reports
+    for (text, date) in complete("") {
+        assert_eq!(parser.parse_relative_to(&text, today).unwrap(), date);
+    }
+    assert!(complete("xyz").is_empty());
+}
+
+#[test]
+fn test_completion_scripts() {
+    let bash = completion_script(Shell::Bash, "my-tool").unwrap();
+    assert!(bash.contains("complete -F _my_tool_dates my-tool"));
+    assert!(bash.contains(" eonw "));
+
+    let zsh = completion_script(Shell::Zsh, "my-tool").unwrap();
+    assert!(zsh.starts_with("#compdef my-tool\n"));
+    assert!(zsh.contains("compdef _my_tool_dates my-tool"));
+
+    let fish = completion_script(Shell::Fish, "my-tool").unwrap();
+    assert!(fish.starts_with("complete -c my-tool -f -a \"today tomorrow"));
+
+    // Phrases would split into several words
+    assert!(!bash.contains("last day of month"));
+
+    // Names that would need quoting are refused
+    for command in ["", "my tool", "tool;rm", "$(tool)", "tool\"", "tool'"] {
+        let err = completion_script(Shell::Bash, command).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            format!("Invalid command name '{}'", command)
+        );
+    }
+}
+
+#[test]
+fn test_locales() {
+    let today = ymd(2026, 10, 18);
+    let parse =
+        |locale: Locale, input: &str| Parser::new().locale(locale).parse_relative_to(input, today);
+
+    assert_eq!(
+        parse(Locale::French, "16 janvier 2025").unwrap(),
+        ymd(2025, 1, 16)
+    );
+    assert_eq!(parse(Locale::French, "Aujourd'hui").unwrap(), today);
+    assert_eq!(
+        parse(Locale::French, "le 3 févr. 2025").unwrap(),
+        ymd(2025, 2, 3)
+    );
+    assert_eq!(
+        parse(Locale::French, "vendredi").unwrap(),
+        ymd(2026, 10, 23)
+    );
+    assert_eq!(
+        parse(Locale::German, "16. Januar 2025").unwrap(),
+        ymd(2025, 1, 16)
+    );
+    assert_eq!(parse(Locale::German, "morgen").unwrap(), ymd(2026, 10, 19));
+    assert_eq!(
+        parse(Locale::German, "16. Mai 2025").unwrap(),
+        ymd(2025, 5, 16)
+    );
+    assert_eq!(
+        parse(Locale::German, "2nd di of märz").unwrap(),
+        ymd(2027, 3, 9)
+    );
+    assert_eq!(
+        parse(Locale::Spanish, "16 de enero de 2025").unwrap(),
+        ymd(2025, 1, 16)
+    );
+    assert_eq!(parse(Locale::Spanish, "mañana").unwrap(), ymd(2026, 10, 19));
+    // English units stuck to digits stay units (`y` is Spanish for and), local
+    // ones are translated
+    assert_eq!(parse(Locale::Spanish, "1y").unwrap(), ymd(2027, 10, 18));
+    assert_eq!(parse(Locale::French, "1an").unwrap(), ymd(2027, 10, 18));
+    assert_eq!(parse(Locale::French, "3jours").unwrap(), ymd(2026, 10, 21));
+    assert_eq!(parse(Locale::German, "3tage").unwrap(), ymd(2026, 10, 21));
+    assert_eq!(
+        parse(Locale::Spanish, "2semanas").unwrap(),
+        ymd(2026, 11, 1)
+    );
+    assert_eq!(parse(Locale::Spanish, "eom+2y").unwrap(), ymd(2028, 10, 31));
+
+    // English words and the rest of the grammar still work
+    assert_eq!(parse(Locale::French, "eom").unwrap(), ymd(2026, 10, 31));
+    assert_eq!(
+        parse(Locale::German, "16 jan 2025").unwrap(),
+        ymd(2025, 1, 16)
+    );
+    assert_eq!(
+        Parser::new()
+            .locale(Locale::French)
+            .parse_range_relative_to("mars 2025", today)
+            .unwrap(),
+        DateRange::new(ymd(2025, 3, 1), ymd(2025, 3, 31))
+    );
+
+    // English does not know the other languages
+    assert!(parse(Locale::English, "demain").is_err());
+
+    assert_eq!("fr".parse::<Locale>().unwrap(), Locale::French);
+    assert_eq!("de_DE.UTF-8".parse::<Locale>().unwrap(), Locale::German);
+    assert_eq!("Spanish".parse::<Locale>().unwrap(), Locale::Spanish);
+    assert_eq!("C".parse::<Locale>().unwrap(), Locale::English);
+    assert!("xx".parse::<Locale>().is_err());
+}
+
+#[test]
+fn test_extract_dates() {
+    let today = ymd(2026, 10, 18);
+    let extract = |text: &str| -> Vec<(String, NaiveDate)> {
+        Parser::new()
+            .extract_dates_relative_to(text, today)
+            .into_iter()
+            .map(|found| (found.expr, found.date))
+            .collect()
+    };
+
+    let text = "call Bob fri about the eom report";
+    let matches = Parser::new().extract_dates_relative_to(text, today);
+    assert_eq!(matches.len(), 2);
+    assert_eq!(matches[0].span, 9..12);
+    assert_eq!(&text[matches[0].span.clone()], "fri");
+    assert_eq!(matches[0].date, ymd(2026, 10, 23));
+    assert_eq!(matches[1].expr, "eom");
+    assert_eq!(matches[1].date, ymd(2026, 10, 31));
+    assert_eq!(strip_dates(text, &matches), "call Bob about the report");
+
+    // Longest match wins, without the sentence's punctuation
+    assert_eq!(
+        extract("Meeting on Tuesday, January 16, 2024 (room 5)."),
+        [("Tuesday, January 16, 2024".to_string(), ymd(2024, 1, 16))]
+    );
+    assert_eq!(
+        extract("pay rent on the last day of next month!"),
+        [("last day of next month".to_string(), ymd(2026, 11, 30))]
+    );
+    assert_eq!(
+        extract("review the 2nd tue of march, then 3d later"),
+        [
+            ("2nd tue of march".to_string(), ymd(2027, 3, 9)),
+            ("3d".to_string(), ymd(2026, 10, 21)),
+        ]
+    );
+
+    // Number words are read as in parse_date
+    assert_eq!(
+        extract("call Bob in three days"),
+        [("three days".to_string(), ymd(2026, 10, 21))]
+    );
+    assert_eq!(
+        extract("meeting in a week"),
+        [("a week".to_string(), ymd(2026, 10, 25))]
+    );
+    assert!(extract("first, call one of them").is_empty());
+
+    // Common words and bare numbers only count inside longer expressions
+    assert!(extract("may I ship version 1.2 in 2024 now?").is_empty());
+    assert_eq!(
+        extract("the sun is out, see you sat 24 oct"),
+        [("sat 24 oct".to_string(), ymd(2026, 10, 24))]
+    );
+
+    // Typos are not corrected
+    assert!(extract("see you tomorow").is_empty());
+    assert!(extract_dates("").is_empty());
+    assert_eq!(strip_dates("nothing here", &[]), "nothing here");
+}
+
+#[test]
+fn test_datetimes() {
+    let now = ymd(2026, 10, 18).and_hms_opt(8, 15, 0).unwrap();
+    let parse = |input: &str| Parser::new().parse_datetime_relative_to(input, now);
+    let at =
+        |date: NaiveDate, hour, minute, second| date.and_hms_opt(hour, minute, second).unwrap();
+
+    assert_eq!(parse("eom 17:00").unwrap(), at(ymd(2026, 10, 31), 17, 0, 0));
+    assert_eq!(
+        parse("tomorrow at 9am").unwrap(),
+        at(ymd(2026, 10, 19), 9, 0, 0)
+    );
+    assert_eq!(
+        parse("Tomorrow at 12am").unwrap(),
+        at(ymd(2026, 10, 19), 0, 0, 0)
+    );
+    assert_eq!(
+        parse("friday 12:30pm").unwrap(),
+        at(ymd(2026, 10, 23), 12, 30, 0)
+    );
+    assert_eq!(
+        parse("16 jan 2024 11:45pm").unwrap(),
+        at(ymd(2024, 1, 16), 23, 45, 0)
+    );
+    assert_eq!(
+        parse("2024-01-16T14:30:05").unwrap(),
+        at(ymd(2024, 1, 16), 14, 30, 5)
+    );
+    assert_eq!(
+        parse("2nd tue of march 8:00").unwrap(),
+        at(ymd(2027, 3, 9), 8, 0, 0)
+    );
+
+    // A time alone is today, a date alone starts at midnight
+    assert_eq!(parse("at 14:30").unwrap(), at(ymd(2026, 10, 18), 14, 30, 0));
+    assert_eq!(parse("5pm").unwrap(), at(ymd(2026, 10, 18), 17, 0, 0));
+    assert_eq!(parse("jan 16").unwrap(), at(ymd(2027, 1, 16), 0, 0, 0));
+    assert_eq!(parse("now").unwrap(), now);
+
+    for input in [
+        "tomorrow 24:00",
+        "tomorrow 13pm",
+        "tomorrow 0am",
+        "eom 12:60",
+    ] {
+        let err = parse(input).unwrap_err();
+        assert_eq!(
+            err.downcast_ref::<ParseError>(),
+            Some(&ParseError::InvalidTime)
+        );
+    }
+    assert!(parse("xyz 12:00").is_err());
+    assert!(parse("tomorrow 14").is_err());
+    assert!(parse_datetime("today 10:00").is_ok());
+}
+
+#[test]
+fn test_natural_from_str() {
+    let due: NaturalDate = " 16 jan 2024 ".parse().unwrap();
+    assert_eq!(due, NaturalDate::new("16 jan 2024", ymd(2024, 1, 16)));
+    assert_eq!(due.to_string(), "2024-01-16");
+    assert_eq!(
+        due.to_string().parse::<NaturalDate>().unwrap().date,
+        due.date
+    );
+
+    let range: NaturalRange = "mar-2025".parse().unwrap();
+    assert_eq!(
+        range.range,
+        DateRange::new(ymd(2025, 3, 1), ymd(2025, 3, 31))
+    );
+    assert_eq!(range.to_string(), "2025-03-01..2025-03-31");
+
+    let datetime: NaturalDateTime = "16/01/2024 9:05pm".parse().unwrap();
+    assert_eq!(datetime.to_string(), "2024-01-16 21:05:00");
+    assert_eq!(
+        datetime
+            .to_string()
+            .parse::<NaturalDateTime>()
+            .unwrap()
+            .datetime,
+        datetime.datetime
+    );
+
+    let err = "tomorow".parse::<NaturalDate>().unwrap_err();
+    assert!(matches!(
+        err.downcast_ref::<ParseError>(),
+        Some(ParseError::Unrecognized { .. })
+    ));
+    assert!("xyz".parse::<NaturalRange>().is_err());
+    assert!("xyz".parse::<NaturalDateTime>().is_err());
+}
+
+#[test]
+fn test_parse_context() {
+    let now = NaiveDate::from_ymd_opt(2024, 1, 16)
+        .unwrap()
+        .and_hms_opt(23, 59, 59)
+        .unwrap();
+    let mut context = ParseContext::new(Parser::new().locale(Locale::French)).now(now);
+    let dates: Vec<_> = context
+        .parse_many(["aujourd'hui", "demain", "eom", "aujourd'hui"])
+        .map(|date| date.unwrap())
+        .collect();
+    assert_eq!(dates[0], now.date());
+    assert_eq!(dates[1], now.date() + Duration::days(1));
+    assert_eq!(dates[2], NaiveDate::from_ymd_opt(2024, 1, 31).unwrap());
+    assert_eq!(dates[3], dates[0]);
+    assert_eq!(context.today(), now.date());
+
+    // Errors are remembered too, still typed
+    for _ in 0..2 {
+        let err = context.parse("xyz").unwrap_err();
+        assert!(matches!(
+            err.downcast_ref::<ParseError>(),
+            Some(ParseError::Unrecognized { .. })
+        ));
+    }
+    for (input, expected) in [
+        ("feb 30 2024", ParseError::InvalidDate),
+        ("99999999999999999999d", ParseError::OutOfRange),
+    ] {
+        for _ in 0..2 {
+            let err = context.parse(input).unwrap_err();
+            assert_eq!(err.downcast_ref::<ParseError>(), Some(&expected));
+        }
+    }
+
+    // The time of day comes from the frozen instant
+    assert_eq!(context.parse_datetime("now").unwrap(), now);
+    assert_eq!(
+        context.parse_range("janvier").unwrap().start,
+        NaiveDate::from_ymd_opt(2024, 1, 1).unwrap()
+    );
+}
+
+#[test]
+fn test_arithmetic() {
+    let parser = Parser::new();
+    let today = NaiveDate::from_ymd_opt(2024, 1, 16).unwrap();
+    let date = |input| parser.parse_relative_to(input, today).unwrap();
+    assert_eq!(
+        date("eom-2d"),
+        NaiveDate::from_ymd_opt(2024, 1, 29).unwrap()
+    );
+    assert_eq!(date("eom - 2days"), date("eom-2d"));
+    assert_eq!(date("friday+1w"), date("friday") + Duration::days(7));
+    assert_eq!(
+        date("2024-03-01-1d"),
+        NaiveDate::from_ymd_opt(2024, 2, 29).unwrap()
+    );
+    assert_eq!(
+        date("15th of eom+1d"),
+        NaiveDate::from_ymd_opt(2024, 2, 15).unwrap()
+    );
+
+    assert!(parser.parse_relative_to("xyz-2d", today).is_err());
+    assert!(parser.parse_relative_to("eom-2", today).is_err());
+}
+
+#[test]
+fn test_keyword_registry() {
+    let today = NaiveDate::from_ymd_opt(2024, 1, 16).unwrap();
+    let release = NaiveDate::from_ymd_opt(2024, 3, 1).unwrap();
+    let mut keywords = KeywordRegistry::new();
+    keywords.register_date("Release", release).unwrap();
+    keywords
+        .register_alias("payday", "last fri of month")
+        .unwrap();
+    keywords
+        .register_alias("fiscal_close", "release - 1w")
+        .unwrap();
+    keywords.register_alias("soon", "three days").unwrap();
+    keywords
+        .register_fn("sprintend", |today| {
+            today.checked_add_signed(Duration::days(
+                13 - today.weekday().num_days_from_monday() as i64,
+            ))
+        })
+        .unwrap();
+    assert_eq!(
+        keywords.names().collect::<Vec<_>>(),
+        ["fiscal_close", "payday", "release", "soon", "sprintend"]
+    );
+
+    // Conflicts with the built-in grammar, earlier keywords and bad names
+    for name in [
+        "eom", "Tomorrow", "nfri", "3d", "march", "of", "release", "pay day", "q-1",
+    ] {
+        assert!(keywords.register_date(name, release).is_err(), "{}", name);
+    }
+    // Aliases only see the keywords registered before them
+    assert!(keywords.register_alias("loop", "loop+1d").is_err());
+    assert!(keywords.register_alias("later", "whenever").is_err());
+
+    let parser = Parser::new().keywords(keywords);
+    let date = |input| parser.parse_relative_to(input, today).unwrap();
+    assert_eq!(date("release"), release);
+    assert_eq!(
+        date("RELEASE-2d"),
+        NaiveDate::from_ymd_opt(2024, 2, 28).unwrap()
+    );
+    assert_eq!(
+        date("payday"),
+        NaiveDate::from_ymd_opt(2024, 1, 26).unwrap()
+    );
+    assert_eq!(
+        date("fiscal_close"),
+        NaiveDate::from_ymd_opt(2024, 2, 23).unwrap()
+    );
+    assert_eq!(
+        date("sprintend"),
+        NaiveDate::from_ymd_opt(2024, 1, 28).unwrap()
+    );
+    assert_eq!(date("soon"), NaiveDate::from_ymd_opt(2024, 1, 19).unwrap());
+    assert_eq!(
+        date("2nd of payday"),
+        NaiveDate::from_ymd_opt(2024, 1, 2).unwrap()
+    );
+
+    // Custom keywords are completed and suggested
+    let completions = parser.complete_relative_to("re", today);
+    assert_eq!(completions[0].text, "release");
+    let err = parser.parse_relative_to("relase", today).unwrap_err();
+    assert_eq!(
+        err.downcast_ref::<ParseError>(),
+        Some(&ParseError::Unrecognized {
+            suggestions: vec!["release".to_string()]
+        })
+    );
+}
+
+#[test]
+fn test_explain() {
+    let today = NaiveDate::from_ymd_opt(2026, 10, 18).unwrap();
+    let explanation = Parser::new().explain_relative_to("1m", today);
+    assert_eq!(explanation.rule(), Some("RELATIVE_TIME"));
+    assert_eq!(explanation.reference, today);
+    assert_eq!(explanation.steps[0].detail, "1 month from 2026-10-18");
+    assert_eq!(
+        explanation.result,
+        Ok(NaiveDate::from_ymd_opt(2026, 11, 18).unwrap())
+    );
+
+    // Nested expressions are listed innermost first
+    let explanation = Parser::new().explain_relative_to("15th of eom+1d", today);
+    let rules: Vec<_> = explanation.steps.iter().map(|step| step.rule).collect();
+    assert_eq!(rules, ["KEYWORD", "ARITHMETIC", "DAY_OF_PERIOD"]);
+    assert_eq!(explanation.steps[1].input, "eom+1d");
+    assert_eq!(
+        explanation.steps[1].date,
+        NaiveDate::from_ymd_opt(2026, 11, 1).unwrap()
+    );
+
+    let explanation = Parser::new()
+        .autocorrect(true)
+        .explain_relative_to("Tomorow", today);
+    assert_eq!(explanation.input, "tomorow");
+    assert_eq!(explanation.rule(), Some("CORRECTION"));
+    assert_eq!(explanation.steps[1].detail, "'tomorow' read as 'tomorrow'");
+
+    let explanation = explain("feb 30 2024");
+    assert!(explanation.steps.is_empty());
+    assert_eq!(explanation.result, Err(ParseError::InvalidDate));
+    assert!(explanation.to_string().ends_with("error: Invalid date\n"));
+
+    // Each failure keeps its own reason
+    assert_eq!(
+        explain("99999999999999999999d").result,
+        Err(ParseError::OutOfRange)
+    );
+    assert_eq!(explain("1d2d").result, Err(ParseError::RepeatedUnit("day")));
+}
+
+#[test]
+fn test_taskwarrior_conformance() {
+    // Named dates as Taskwarrior resolves them on Wednesday 2024-01-17
+    let parser = Parser::new().taskwarrior(true);
+    let today = NaiveDate::from_ymd_opt(2024, 1, 17).unwrap();
+    #[rustfmt::skip]
+    let table = [
+        ("now", "2024-01-17"), ("today", "2024-01-17"),
+        ("sod", "2024-01-17"), ("eod", "2024-01-17"),
+        ("yesterday", "2024-01-16"), ("tomorrow", "2024-01-18"),
+        ("sopd", "2024-01-16"), ("eopd", "2024-01-16"),
+        ("sond", "2024-01-18"), ("eond", "2024-01-18"),
+        ("sow", "2024-01-15"), ("socw", "2024-01-15"),
+        ("eow", "2024-01-21"), ("eocw", "2024-01-21"),
+        ("sopw", "2024-01-08"), ("eopw", "2024-01-14"),
+        ("sonw", "2024-01-22"), ("eonw", "2024-01-28"),
+        ("soww", "2024-01-15"), ("eoww", "2024-01-19"),
+        ("som", "2024-01-01"), ("socm", "2024-01-01"),
+        ("eom", "2024-01-31"), ("eocm", "2024-01-31"),
+        ("sopm", "2023-12-01"), ("eopm", "2023-12-31"),
+        ("sonm", "2024-02-01"), ("eonm", "2024-02-29"),
+        ("soq", "2024-01-01"), ("socq", "2024-01-01"),
+        ("eoq", "2024-03-31"), ("eocq", "2024-03-31"),
+        ("sopq", "2023-10-01"), ("eopq", "2023-12-31"),
+        ("sonq", "2024-04-01"), ("eonq", "2024-06-30"),
+        ("soy", "2024-01-01"), ("socy", "2024-01-01"),
+        ("eoy", "2024-12-31"), ("eocy", "2024-12-31"),
+        ("sopy", "2023-01-01"), ("eopy", "2023-12-31"),
+        ("sony", "2025-01-01"), ("eony", "2025-12-31"),
+        ("monday", "2024-01-22"), ("wednesday", "2024-01-24"), ("fri", "2024-01-19"),
+        ("january", "2025-01-01"), ("feb", "2024-02-01"), ("december", "2024-12-01"),
+        ("1st", "2024-02-01"), ("18th", "2024-01-18"),
+        ("later", "9999-12-30"), ("someday", "9999-12-30"),
+        ("easter", "2024-03-31"), ("goodfriday", "2024-03-29"),
+        ("eastermonday", "2024-04-01"), ("ascension", "2024-05-09"),
+        ("pentecost", "2024-05-19"),
+        ("midsommar", "2024-06-22"), ("midsommarafton", "2024-06-21"),
+        ("P1D", "2024-01-18"), ("P1W", "2024-01-24"), ("P2W3D", "2024-02-03"),
+        ("P1M", "2024-02-17"), ("P1Y2M3D", "2025-03-20"), ("PT36H", "2024-01-18"),
+    ];
+    for (input, expected) in table {
+        assert_eq!(
+            parser.parse_relative_to(input, today).unwrap().to_string(),
+            expected,
+            "{}",
+            input
+        );
+    }
+
+    // Holidays already past this year are next year's
+    let summer = NaiveDate::from_ymd_opt(2024, 6, 23).unwrap();
+    assert_eq!(
+        parser.parse_relative_to("easter", summer).unwrap(),
+        NaiveDate::from_ymd_opt(2025, 4, 20).unwrap()
+    );
+    assert_eq!(
+        parser.parse_relative_to("midsommar", summer).unwrap(),
+        NaiveDate::from_ymd_opt(2025, 6, 21).unwrap()
+    );
+
+    // Periods end at the last second and durations count from now
+    let now = today.and_hms_opt(14, 30, 0).unwrap();
+    #[rustfmt::skip]
+    let table = [
+        ("now", "2024-01-17 14:30:00"), ("sod", "2024-01-17 00:00:00"),
+        ("eod", "2024-01-17 23:59:59"), ("eom", "2024-01-31 23:59:59"),
+        ("sow", "2024-01-15 00:00:00"), ("eoww", "2024-01-19 23:59:59"),
+        ("PT2H30M", "2024-01-17 17:00:00"), ("P1D", "2024-01-18 14:30:00"),
+        ("eod 17:00", "2024-01-17 17:00:00"),
+    ];
+    for (input, expected) in table {
+        assert_eq!(
+            parser
+                .parse_datetime_relative_to(input, now)
+                .unwrap()
+                .to_string(),
+            expected,
+            "{}",
+            input
+        );
+    }
+
+    for input in ["p", "pt", "p1h", "pt1d", "p1d1m", "eopww", "sox"] {
+        assert!(parser.parse_relative_to(input, today).is_err(), "{}", input);
+    }
+
+    // The default grammar keeps its own meanings
+    assert_eq!(parse_date("sow").unwrap(), parse_date("monday").unwrap());
+    assert!(parse_date("sod").is_err());
+    assert!(parse_date("P1D").is_err());
+    assert_eq!(
+        parser.explain_relative_to("eopm", today).rule(),
+        Some("TASKWARRIOR")
+    );
+    assert_eq!(parser.complete_relative_to("eop", today).len(), 5);
+}
+
+#[test]
+fn test_sentinels() {
+    let today = NaiveDate::from_ymd_opt(2024, 1, 16).unwrap();
+    assert_eq!(parse_resolved("later").unwrap(), Resolved::Never);
+    assert_eq!(parse_resolved(" Someday ").unwrap(), Resolved::Never);
+    assert_eq!(
+        Parser::new()
+            .parse_resolved_relative_to("tomorrow", today)
+            .unwrap(),
+        Resolved::Date(NaiveDate::from_ymd_opt(2024, 1, 17).unwrap())
+    );
+    assert!(parse_resolved("whenever").is_err());
+
+    // Sentinels are not dates, even in Taskwarrior mode
+    let err = parse_date("someday").unwrap_err();
+    assert_eq!(
+        err.downcast_ref::<ParseError>(),
+        Some(&ParseError::Sentinel)
+    );
+    assert!(parse_date("later+1d").is_err());
+    let taskwarrior = Parser::new().taskwarrior(true);
+    assert_eq!(
+        taskwarrior.parse_resolved("later").unwrap(),
+        Resolved::Never
+    );
+    assert_eq!(
+        taskwarrior.parse("later").unwrap(),
+        NaiveDate::from_ymd_opt(9999, 12, 30).unwrap()
+    );
+
+    // Sentinels sort around every date
+    let date = Resolved::Date(NaiveDate::MAX);
+    assert!(Resolved::Always < Resolved::Date(NaiveDate::MIN));
+    assert!(date < Resolved::Never);
+    assert_eq!(date.date(), Some(NaiveDate::MAX));
+    assert_eq!(Resolved::Never.date(), None);
+}
+
+#[test]
+fn test_open_intervals() {
+    let today = NaiveDate::from_ymd_opt(2024, 1, 16).unwrap();
+    let ymd = |year, month, day| NaiveDate::from_ymd_opt(year, month, day).unwrap();
+    let interval = |input| {
+        Parser::new()
+            .parse_interval_relative_to(input, today)
+            .unwrap()
+    };
+    let date = |year, month, day| Resolved::Date(ymd(year, month, day));
+
+    assert_eq!(
+        interval("..eom"),
+        Interval::new(Resolved::Always, date(2024, 1, 31))
+    );
+    assert_eq!(
+        interval("2024-01-01.."),
+        Interval::new(date(2024, 1, 1), Resolved::Never)
+    );
+    assert_eq!(
+        interval("today .. 2w"),
+        Interval::new(date(2024, 1, 16), date(2024, 1, 30))
+    );
+    assert_eq!(
+        interval("jan..march"),
+        Interval::new(date(2024, 1, 1), date(2024, 3, 31))
+    );
+    assert_eq!(
+        interval("tomorrow..later"),
+        Interval::new(date(2024, 1, 17), Resolved::Never)
+    );
+    assert_eq!(
+        interval(".."),
+        Interval::new(Resolved::Always, Resolved::Never)
+    );
+    assert_eq!(
+        interval("march"),
+        Interval::new(date(2024, 3, 1), date(2024, 3, 31))
+    );
+    assert_eq!(
+        interval("someday"),
+        Interval::new(Resolved::Never, Resolved::Never)
+    );
+
+    let open = interval("..eom");
+    assert!(open.contains(ymd(1900, 1, 1)));
+    assert!(open.contains(ymd(2024, 1, 31)));
+    assert!(!open.contains(ymd(2024, 2, 1)));
+    assert!(interval("2024-01-01..").contains(ymd(9999, 1, 1)));
+    assert!(!interval("eom..today").contains(ymd(2024, 1, 20)));
+
+    assert!(parse_interval("1..2..3").is_err());
+    assert!(parse_interval("..whenever").is_err());
+    assert!(parse_interval("..feb 30").is_err());
+}
+
+#[test]
+fn test_date_predicates() {
+    let today = NaiveDate::from_ymd_opt(2024, 1, 16).unwrap();
+    let ymd = |year, month, day| NaiveDate::from_ymd_opt(year, month, day).unwrap();
+    let predicate = |filter| {
+        Parser::new()
+            .parse_predicate_relative_to(filter, today)
+            .unwrap()
+    };
+    let matching = |filter| -> Vec<u32> {
+        let predicate = predicate(filter);
+        (1..=31)
+            .filter(|day| predicate.matches(ymd(2024, 1, *day)))
+            .collect()
+    };
+
+    assert_eq!(
+        predicate("before:eow"),
+        DatePredicate::Before(Resolved::Date(ymd(2024, 1, 21)))
+    );
+    assert_eq!(matching("before:2024-01-03"), [1, 2]);
+    assert_eq!(matching("after:2024-01-29"), [30, 31]);
+    assert_eq!(matching("by:2024-01-02"), [1, 2]);
+    assert_eq!(matching("on:fri"), [19]);
+    assert_eq!(matching("On 2024-01-05"), [5]);
+    assert_eq!(matching("within:1w"), [16, 17, 18, 19, 20, 21, 22, 23]);
+    assert_eq!(matching("within:-2d"), [14, 15, 16]);
+    assert_eq!(matching("between 2024-01-10 and 2024-01-12"), [10, 11, 12]);
+    assert_eq!(matching("between:29th and eom"), [29, 30, 31]);
+
+    // Periods count whole: before their first day, after their last one
+    assert!(predicate("before:feb").matches(ymd(2024, 1, 31)));
+    assert!(!predicate("before:feb").matches(ymd(2024, 2, 1)));
+    assert!(predicate("after:2024").matches(ymd(2025, 1, 1)));
+    assert!(!predicate("after:2024").matches(ymd(2024, 12, 31)));
+    assert!(predicate("on:march").matches(ymd(2024, 3, 20)));
+
+    // Open intervals and sentinels
+    assert!(predicate("on:..eom").matches(ymd(1990, 5, 5)));
+    assert!(predicate("before:someday").matches(NaiveDate::MAX));
+    assert!(!predicate("after:someday").matches(NaiveDate::MAX));
+    assert!(predicate("within:later").matches(ymd(3000, 1, 1)));
+
+    // An attribute in front of the comparator is skipped
+    assert_eq!(predicate("due.before:eow"), predicate("before:eow"));
+    assert_eq!(predicate("Scheduled.on fri"), predicate("on:fri"));
+    assert_eq!(
+        matching("wait.between 2024-01-10 and 2024-01-12"),
+        [10, 11, 12]
+    );
+
+    let error = |filter| parse_predicate(filter).unwrap_err();
+    assert_eq!(
+        error("until:eom").to_string(),
+        "Unknown comparator 'until', expected before, after, on, by, within or between"
+    );
+    assert_eq!(error("before").to_string(), "Missing date after 'before'");
+    assert_eq!(error("before:").to_string(), "Missing date after 'before'");
+    assert_eq!(error("on: ").to_string(), "Missing date after 'on'");
+    assert_eq!(error("due.on:").to_string(), "Missing date after 'on'");
+    assert_eq!(
+        error(".before:eow").to_string(),
+        "Unknown comparator '.before', expected before, after, on, by, within or between"
+    );
+    assert_eq!(
+        error("due.until:eom").to_string(),
+        "Unknown comparator 'until', expected before, after, on, by, within or between"
+    );
+    assert_eq!(
+        error("between today").to_string(),
+        "Expected 'between <date> and <date>'"
+    );
+    assert_eq!(
+        error("on:feb 30 2024").downcast_ref::<ParseError>(),
+        Some(&ParseError::InvalidDate)
+    );
+}
+
+#[test]
+fn test_parse_duration() {
+    let duration = |input: &str| parse_duration(input).unwrap();
+    let parts = |input| {
+        let duration = duration(input);
+        (
+            duration.years(),
+            duration.months(),
+            duration.days(),
+            duration.seconds(),
+        )
+    };
+
+    assert_eq!(parts("1w2d"), (0, 0, 9, 0));
+    assert_eq!(parts("3 weeks"), (0, 0, 21, 0));
+    assert_eq!(parts("1 week 3 days"), (0, 0, 10, 0));
+    assert_eq!(parts("2 years, 6 months"), (2, 6, 0, 0));
+    assert_eq!(parts("1 day and 2 hours"), (0, 0, 1, 7_200));
+    assert_eq!(parts("14m"), (1, 2, 0, 0));
+    assert_eq!(parts("1.5d"), (0, 0, 1, 43_200));
+    assert_eq!(parts("1.5w"), (0, 0, 10, 43_200));
+    assert_eq!(parts("1.5m"), (0, 1, 15, 0));
+    assert_eq!(parts("0.5y"), (0, 6, 0, 0));
+    assert_eq!(parts("2h30min"), (0, 0, 0, 9_000));
+    assert_eq!(parts("-1m2w"), (0, -1, -14, 0));
+    assert_eq!(parts("P1M2D"), (0, 1, 2, 0));
+    assert_eq!(parts("p1y2m3w4dt5h6m7s"), (1, 2, 25, 18_367));
+    assert_eq!(parts("PT36H"), (0, 0, 1, 43_200));
+    assert_eq!(parts("P0.5D"), (0, 0, 0, 43_200));
+    assert_eq!(parts("0d"), (0, 0, 0, 0));
+    assert_eq!(duration("0.1s").nanoseconds(), 100_000_000);
+    assert_eq!(duration("1.5min").nanoseconds(), 0);
+
+    // Written back in units, largest first
+    for (input, expected) in [
+        ("1 week 2 days", "1w2d"),
+        ("P1Y14M", "2y2m"),
+        ("36h", "1d12h"),
+        ("-1.5d", "-1d12h"),
+        ("90s", "1min30s"),
+        ("0w", "0d"),
+        ("0.1s", "0.1s"),
+        ("1.25s", "1.25s"),
+        ("-PT0.5S", "-0.5s"),
+        ("1min0.001s", "1min0.001s"),
+    ] {
+        let written = duration(input).to_string();
+        assert_eq!(written, expected);
+        assert_eq!(duration(&written), duration(input));
+    }
+
+    // Months and years follow the calendar
+    let ymd = |year, month, day| NaiveDate::from_ymd_opt(year, month, day).unwrap();
+    assert_eq!(
+        duration("1m").add_to(ymd(2024, 1, 31)),
+        Some(ymd(2024, 2, 29))
+    );
+    assert_eq!(
+        duration("1y").add_to(ymd(2024, 2, 29)),
+        Some(ymd(2025, 2, 28))
+    );
+    assert_eq!(
+        duration("1m1d").add_to(ymd(2024, 1, 31)),
+        Some(ymd(2024, 3, 1))
+    );
+    assert_eq!(
+        duration("-1m2w").add_to(ymd(2024, 3, 31)),
+        Some(ymd(2024, 2, 15))
+    );
+    assert_eq!(
+        duration("1.5d").add_to(ymd(2024, 1, 1)),
+        Some(ymd(2024, 1, 2))
+    );
+    assert_eq!(
+        duration("1d2h").add_to_datetime(ymd(2024, 1, 1).and_hms_opt(23, 0, 0).unwrap()),
+        Some(ymd(2024, 1, 3).and_hms_opt(1, 0, 0).unwrap())
+    );
+    assert_eq!(duration("1000000y").add_to(ymd(2024, 1, 1)), None);
+
+    // Compared when one is longer in both months and time
+    assert!(duration("1w2d") < duration("2w"));
+    assert!(duration("1y") > duration("11m"));
+    assert!(duration("1m1d") > duration("1m"));
+    assert_eq!(duration("1y"), duration("12m"));
+    assert_eq!(duration("1w"), duration("7d"));
+    assert_eq!(duration("1m").partial_cmp(&duration("30d")), None);
+    assert!(duration("-1d") < CalendarDuration::default());
+    assert_eq!(-duration("1w"), duration("-7d"));
+
+    let error = |input| {
+        parse_duration(input)
+            .unwrap_err()
+            .downcast::<ParseError>()
+            .unwrap()
+    };
+    assert_eq!(error("1d2d"), ParseError::RepeatedUnit("day"));
+    assert_eq!(error("1x").to_string(), "Unrecognized duration format");
+    assert_eq!(
+        error("1 week 2 weeks").to_string(),
+        "The unit 'week' is given twice"
+    );
+    for input in [
+        "", "d", "1", "1x", "1d and", "1d,", "p", "pt", "p1h", "pt1d", "p1d1y", "p1 d",
+    ] {
+        assert!(
+            matches!(error(input), ParseError::UnrecognizedDuration),
+            "{}",
+            input
+        );
+    }
+    assert_eq!(error("99999999999999999999d"), ParseError::OutOfRange);
+    assert!("2 fortnights".parse::<CalendarDuration>().is_err());
+}
+
+#[test]
+fn test_compound_relative_units() {
+    let today = NaiveDate::from_ymd_opt(2024, 1, 31).unwrap();
+    let ymd = |year, month, day| NaiveDate::from_ymd_opt(year, month, day).unwrap();
+    let date = |input| Parser::new().parse_relative_to(input, today).unwrap();
+
+    assert_eq!(date("1w3d"), ymd(2024, 2, 10));
+    assert_eq!(date("1 week 3 days"), ymd(2024, 2, 10));
+    assert_eq!(date("1 week and 3 days"), ymd(2024, 2, 10));
+    assert_eq!(date("1 week, 3d"), ymd(2024, 2, 10));
+    assert_eq!(date("2y6m"), ymd(2026, 7, 31));
+    assert_eq!(date("1y2m3w4d"), ymd(2025, 4, 25));
+
+    // Larger units first, with calendar months: January 31st plus a month is
+    // February 29th
+    assert_eq!(date("1m1d"), ymd(2024, 3, 1));
+    assert_eq!(date("1d1m"), ymd(2024, 3, 1));
+    assert_eq!(date("-1m2w"), ymd(2023, 12, 17));
+    assert_eq!(date("eom+1m2d"), ymd(2024, 3, 2));
+    assert_eq!(date("2024-03-31 - 1m2w"), ymd(2024, 2, 15));
+
+    // A single unit follows the calendar too, so a zero amount changes nothing
+    assert_eq!(date("1m"), ymd(2024, 2, 29));
+    assert_eq!(date("1m0d"), date("1m"));
+    assert_eq!(date("1y0m"), date("1y"));
+    assert_eq!(date("1 month"), date("1m"));
+    assert_eq!(date("12m"), date("1y"));
+    assert_eq!(date("eom+1m"), ymd(2024, 2, 29));
+    assert_eq!(date("3 days"), ymd(2024, 2, 3));
+    assert_eq!(date("friday + 2 weeks"), ymd(2024, 2, 16));
+
+    let error = |input| {
+        parse_date(input)
+            .unwrap_err()
+            .downcast::<ParseError>()
+            .unwrap()
+    };
+    assert_eq!(error("1d2d"), ParseError::RepeatedUnit("day"));
+    assert_eq!(error("1 week 2 wks"), ParseError::RepeatedUnit("week"));
+    assert_eq!(error("eom+1m2months"), ParseError::RepeatedUnit("month"));
+    assert_eq!(error("1y1yr").to_string(), "The unit 'year' is given twice");
+    assert!(parse_date("1w 3 d").is_err());
+    assert!(parse_date("1w3d and").is_err());
+
+    let explanation = Parser::new().explain_relative_to("-1m2w", today);
+    assert_eq!(explanation.steps[0].detail, "-1m2w from 2024-01-31");
+    let explanation = Parser::new().explain_relative_to("eom+1m2d", today);
+    assert_eq!(explanation.steps[1].detail, "2024-01-31 plus 1m2d");
+}
+
+#[test]
+fn test_number_words() {
+    let today = NaiveDate::from_ymd_opt(2024, 1, 17).unwrap();
+    let ymd = |year, month, day| NaiveDate::from_ymd_opt(year, month, day).unwrap();
+    let date = |input| Parser::new().parse_relative_to(input, today).unwrap();
+
+    assert_eq!(date("three days"), ymd(2024, 1, 20));
+    assert_eq!(date("a week"), ymd(2024, 1, 24));
+    assert_eq!(date("a couple of days"), ymd(2024, 1, 19));
+    assert_eq!(date("a few days"), ymd(2024, 1, 20));
+    assert_eq!(date("fortnight"), ymd(2024, 1, 31));
+    assert_eq!(date("two fortnights"), ymd(2024, 2, 14));
+    assert_eq!(date("thirty-one days"), ymd(2024, 2, 17));
+    assert_eq!(date("one hundred days"), ymd(2024, 4, 26));
+    assert_eq!(date("one week and three days"), ymd(2024, 1, 27));
+    assert_eq!(date("tomorrow + two weeks"), ymd(2024, 2, 1));
+    assert_eq!(date("-Two Days"), ymd(2024, 1, 15));
+
+    // Ordinals feed every day slot
+    assert_eq!(date("twenty-first"), ymd(2024, 1, 21));
+    assert_eq!(date("twenty-first of march"), ymd(2024, 3, 21));
+    assert_eq!(date("twenty-first of next month"), ymd(2024, 2, 21));
+    assert_eq!(date("first day of next month"), ymd(2024, 2, 1));
+    assert_eq!(date("3rd day of march"), ymd(2024, 3, 3));
+    assert_eq!(date("second tuesday of march"), ymd(2024, 3, 12));
+
+    // An ordinal weekday on its own is the next one in any month
+    assert_eq!(date("first monday"), ymd(2024, 2, 5));
+    assert_eq!(date("3rd wednesday"), ymd(2024, 2, 21));
+    assert_eq!(date("fifth friday"), ymd(2024, 3, 29));
+    assert!(parse_date("last friday").is_err());
+    assert!(parse_date("1 mon").is_err());
+
+    let vague = Parser::new().couple(3).few(5);
+    assert_eq!(
+        vague.parse_relative_to("a couple of days", today).unwrap(),
+        ymd(2024, 1, 20)
+    );
+    assert_eq!(
+        vague.parse_relative_to("a few weeks", today).unwrap(),
+        ymd(2024, 2, 21)
+    );
+
+    let duration = |input| parse_duration(input).unwrap().to_string();
+    assert_eq!(duration("half a day"), "12h");
+    assert_eq!(duration("an hour"), "1h");
+    assert_eq!(duration("one and a half hours"), "1h30min");
+    assert_eq!(duration("a fortnight"), "2w");
+    assert_eq!(duration("twenty-five minutes"), "25min");
+
+    let french = |input| {
+        Parser::new()
+            .locale(Locale::French)
+            .parse_relative_to(input, today)
+            .unwrap()
+    };
+    assert_eq!(french("trois jours"), ymd(2024, 1, 20));
+    assert_eq!(french("sept jours"), ymd(2024, 1, 24));
+    assert_eq!(french("16 sept"), ymd(2024, 9, 16));
+    assert_eq!(french("16 sept 2024"), ymd(2024, 9, 16));
+    assert_eq!(french("sept 2025"), ymd(2025, 9, 1));
+    assert_eq!(french("deux semaines et trois jours"), ymd(2024, 2, 3));
+    assert_eq!(french("le vingt et un mars"), ymd(2024, 3, 21));
+    assert_eq!(french("le premier mars"), ymd(2024, 3, 1));
+
+    let german = |input| {
+        Parser::new()
+            .locale(Locale::German)
+            .parse_relative_to(input, today)
+            .unwrap()
+    };
+    assert_eq!(german("drei Tage"), ymd(2024, 1, 20));
+    assert_eq!(german("ein paar Tage"), ymd(2024, 1, 19));
+    assert_eq!(german("am einundzwanzigsten März"), ymd(2024, 3, 21));
+    assert_eq!(german("zwei Wochen und drei Tage"), ymd(2024, 2, 3));
+
+    let spanish = |input| {
+        Parser::new()
+            .locale(Locale::Spanish)
+            .parse_relative_to(input, today)
+            .unwrap()
+    };
+    assert_eq!(spanish("tres días"), ymd(2024, 1, 20));
+    assert_eq!(spanish("un par de días"), ymd(2024, 1, 19));
+    assert_eq!(spanish("treinta y un días"), ymd(2024, 2, 17));
+    assert_eq!(spanish("el veintiuno de marzo"), ymd(2024, 3, 21));
+
+    // Number words are not free keyword names, unless part of a longer one
+    let mut keywords = KeywordRegistry::new();
+    assert!(keywords.register_alias("three", "eom").is_err());
+    assert!(keywords.register_alias("one_off", "eom").is_ok());
+}